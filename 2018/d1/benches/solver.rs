@@ -0,0 +1,8 @@
+extern crate common;
+extern crate d1;
+use common::bench::bench_solution;
+use d1::ChronalCalibration;
+
+fn main() {
+    bench_solution::<ChronalCalibration>("d1");
+}
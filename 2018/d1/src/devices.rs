@@ -0,0 +1,413 @@
+use frequency::{parse_frequency_adjustments, parse_line, AdjustmentError, Frequency, Repeat};
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A chronal device with its own stream of adjustments to calibrate.
+pub struct Device {
+    pub name: String,
+    /// The frequency the device starts at.
+    pub start: i64,
+    pub adjustments: Vec<i32>,
+}
+
+/// Split an input into a device for each section. Each section starts with a header naming the
+/// device, optionally with the frequency it starts at, followed by its adjustments: -
+///
+/// [alpha]
+/// +1, -2
+/// [beta start=-20]
+/// +3
+///
+/// Devices without a start in their header start at `default_start`.
+pub fn parse_devices(input: &str, default_start: i64) -> Result<Vec<Device>, DeviceError> {
+    let mut devices: Vec<Device> = vec![];
+    let mut position = 0;
+    for (index, line) in input.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            let device = parse_header(trimmed, default_start).ok_or(DeviceError::Header {
+                line: index + 1,
+                text: trimmed.to_string(),
+            })?;
+            add_device(&mut devices, device)?;
+            position = 0;
+            continue;
+        }
+        match devices.last_mut() {
+            Some(device) => parse_line(line, index + 1, &mut position, &mut device.adjustments)
+                .map_err(|err| DeviceError::Adjustments {
+                    name: device.name.clone(),
+                    err,
+                })?,
+            None if trimmed.is_empty() => {}
+            None => return Err(DeviceError::Unnamed { line: index + 1 }),
+        }
+    }
+    if devices.is_empty() {
+        return Err(DeviceError::NoDevices);
+    }
+    Ok(devices)
+}
+
+/// Read a device from each file in the directory, named after the file without its extension and
+/// starting at `default_start`. Hidden files are skipped and the devices are sorted by name.
+pub fn read_devices<P: AsRef<Path>>(
+    dir: P,
+    default_start: i64,
+) -> Result<Vec<Device>, DeviceError> {
+    let dir = dir.as_ref();
+    let read_err = |path: &Path, err: io::Error| DeviceError::Read {
+        path: path.to_path_buf(),
+        err,
+    };
+
+    let mut paths: Vec<PathBuf> = vec![];
+    for entry in fs::read_dir(dir).map_err(|err| read_err(dir, err))? {
+        let path = entry.map_err(|err| read_err(dir, err))?.path();
+        let hidden = path
+            .file_name()
+            .is_none_or(|name| name.to_string_lossy().starts_with('.'));
+        if path.is_file() && !hidden {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut devices: Vec<Device> = vec![];
+    for path in paths {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let contents = fs::read_to_string(&path).map_err(|err| read_err(&path, err))?;
+        let adjustments =
+            parse_frequency_adjustments(&contents).map_err(|err| DeviceError::Adjustments {
+                name: name.clone(),
+                err,
+            })?;
+        add_device(
+            &mut devices,
+            Device {
+                name,
+                start: default_start,
+                adjustments,
+            },
+        )?;
+    }
+    if devices.is_empty() {
+        return Err(DeviceError::NoDevices);
+    }
+    Ok(devices)
+}
+
+/// Read `[name]` or `[name start=N]`.
+fn parse_header(header: &str, default_start: i64) -> Option<Device> {
+    if !header.ends_with(']') {
+        return None;
+    }
+    let mut words = header[1..header.len() - 1].split_whitespace();
+    let name = words.next()?.to_string();
+    let start = match words.next() {
+        Some(word) if word.starts_with("start=") => word["start=".len()..].parse().ok()?,
+        Some(_) => return None,
+        None => default_start,
+    };
+    if words.next().is_some() {
+        return None;
+    }
+    Some(Device {
+        name,
+        start,
+        adjustments: vec![],
+    })
+}
+
+fn add_device(devices: &mut Vec<Device>, device: Device) -> Result<(), DeviceError> {
+    if devices.iter().any(|existing| existing.name == device.name) {
+        return Err(DeviceError::Duplicate(device.name));
+    }
+    devices.push(device);
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Where a device ended up after one pass through its adjustments and the first frequency it
+/// reached twice.
+pub struct Calibrated {
+    pub value: i64,
+    pub repeat: Repeat,
+}
+
+#[derive(Debug)]
+/// The outcome of calibrating a single device.
+pub struct Calibration {
+    pub name: String,
+    pub start: i64,
+    pub outcome: Result<Calibrated, AdjustmentError>,
+}
+
+/// Calibrate every device, sharing them out between a thread for each available core. The
+/// calibrations come back in the same order as the devices.
+pub fn calibrate(devices: &[Device]) -> Vec<Calibration> {
+    let workers = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(devices.len())
+        .max(1);
+
+    let mut calibrations: Vec<(usize, Calibration)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|worker| {
+                scope.spawn(move || {
+                    devices
+                        .iter()
+                        .enumerate()
+                        .skip(worker)
+                        .step_by(workers)
+                        .map(|(index, device)| (index, calibrate_device(device)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        // a worker that panicked would leave its devices out of the table, so pass the panic on.
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    });
+    calibrations.sort_by_key(|(index, _)| *index);
+    calibrations
+        .into_iter()
+        .map(|(_, calibration)| calibration)
+        .collect()
+}
+
+fn calibrate_device(device: &Device) -> Calibration {
+    let outcome = if device.adjustments.is_empty() {
+        Err(AdjustmentError::Empty)
+    } else {
+        let mut freq = Frequency::new(device.start);
+        freq.adjust_frequency(&device.adjustments)
+            .and_then(|()| Frequency::new(device.start).first_repeat_by_drift(&device.adjustments))
+            .map(|repeat| Calibrated {
+                value: freq.value,
+                repeat,
+            })
+    };
+    Calibration {
+        name: device.name.clone(),
+        start: device.start,
+        outcome,
+    }
+}
+
+/// Lay the calibrations out as a table with a row for each device.
+pub fn table(calibrations: &[Calibration]) -> String {
+    let mut rows: Vec<[String; 4]> = vec![[
+        "device".to_string(),
+        "start".to_string(),
+        "final".to_string(),
+        "first repeat".to_string(),
+    ]];
+    for calibration in calibrations {
+        let (value, repeat) = match calibration.outcome {
+            Ok(Calibrated {
+                value,
+                repeat: Repeat::Found { frequency, .. },
+            }) => (value.to_string(), frequency.to_string()),
+            Ok(Calibrated {
+                value,
+                repeat: Repeat::Never,
+            }) => (value.to_string(), "never".to_string()),
+            Err(ref err) => ("-".to_string(), format!("error: {}", err)),
+        };
+        rows.push([
+            calibration.name.clone(),
+            calibration.start.to_string(),
+            value,
+            repeat,
+        ]);
+    }
+
+    let mut widths = [0; 4];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let mut table = String::new();
+    for row in &rows {
+        let line = format!(
+            "{:<w0$}  {:>w1$}  {:>w2$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        );
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+#[derive(Debug)]
+/// Something that stopped the devices from being read.
+pub enum DeviceError {
+    /// A section header that isn't `[name]` or `[name start=N]`, the line counted from 1.
+    Header { line: usize, text: String },
+    /// Adjustments came before the first section header.
+    Unnamed { line: usize },
+    /// Two devices share a name.
+    Duplicate(String),
+    /// There were no devices at all.
+    NoDevices,
+    /// A device's adjustments couldn't be read.
+    Adjustments { name: String, err: AdjustmentError },
+    /// A file or directory couldn't be read.
+    Read { path: PathBuf, err: io::Error },
+}
+
+impl fmt::Display for DeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeviceError::Header { line, text } => write!(
+                f,
+                "line {}: expected a header like [name] or [name start=N], found {:?}",
+                line, text
+            ),
+            DeviceError::Unnamed { line } => {
+                write!(f, "line {}: adjustments before the first device", line)
+            }
+            DeviceError::Duplicate(name) => write!(f, "more than one device named {:?}", name),
+            DeviceError::NoDevices => write!(f, "there are no devices to calibrate"),
+            DeviceError::Adjustments { name, err } => write!(f, "device {}: {}", name, err),
+            DeviceError::Read { path, err } => {
+                write!(f, "failed to read {}: {}", path.display(), err)
+            }
+        }
+    }
+}
+
+impl error::Error for DeviceError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            DeviceError::Adjustments { err, .. } => Some(err),
+            DeviceError::Read { err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_parse_devices() {
+    let input = "\n[alpha]\n+1, -2\n+3\n\n[beta start=-20]\n+3 +3 +4 -2 -4\n[gamma]\n";
+    let devices = parse_devices(input, 7).unwrap();
+    assert_eq!(
+        devices,
+        vec![
+            Device {
+                name: "alpha".to_string(),
+                start: 7,
+                adjustments: vec![1, -2, 3],
+            },
+            Device {
+                name: "beta".to_string(),
+                start: -20,
+                adjustments: vec![3, 3, 4, -2, -4],
+            },
+            Device {
+                name: "gamma".to_string(),
+                start: 7,
+                adjustments: vec![],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_devices_errors() {
+    let err = parse_devices("[alpha]\n+1\n[beta start=x]\n", 0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 3: expected a header like [name] or [name start=N], found \"[beta start=x]\""
+    );
+    let err = parse_devices("+1\n[alpha]\n", 0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 1: adjustments before the first device"
+    );
+    let err = parse_devices("[alpha]\n+1\n[alpha]\n", 0).unwrap_err();
+    assert_eq!(err.to_string(), "more than one device named \"alpha\"");
+    let err = parse_devices("[alpha]\n+1\n[beta]\n+2, two\n", 0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "device beta: line 4, adjustment 2: couldn't read \"two\", invalid digit found in string"
+    );
+}
+
+#[test]
+fn test_calibrate_in_order() {
+    let devices: Vec<Device> = (0..20)
+        .map(|n| Device {
+            name: format!("d{}", n),
+            start: n,
+            adjustments: vec![3, 3, 4, -2, -4],
+        })
+        .collect();
+    let calibrations = calibrate(&devices);
+    assert_eq!(calibrations.len(), 20);
+    for (n, calibration) in calibrations.iter().enumerate() {
+        let n = n as i64;
+        assert_eq!(calibration.name, format!("d{}", n));
+        match calibration.outcome {
+            Ok(Calibrated {
+                value,
+                repeat: Repeat::Found { frequency, .. },
+            }) => assert_eq!((value, frequency), (n + 4, n + 10)),
+            ref other => panic!("unexpected outcome {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_table() {
+    let devices = parse_devices(
+        "[alpha]\n+1, -1\n[beta start=-20]\n+1, +2\n[gamma]\n[delta start=9223372036854775807]\n+1",
+        0,
+    )
+    .unwrap();
+    assert_eq!(
+        table(&calibrate(&devices)),
+        "device                start  final  first repeat\n\
+         alpha                     0      0  0\n\
+         beta                    -20    -17  never\n\
+         gamma                     0      -  error: there are no adjustments to apply\n\
+         delta   9223372036854775807      -  error: the frequency went beyond what a 64 bit integer can hold\n"
+    );
+}
+
+#[test]
+fn test_read_devices() {
+    let dir = std::env::temp_dir().join(format!("d1-devices-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("beta.txt"), "+1\n-2\n").unwrap();
+    fs::write(dir.join("alpha"), "+3, +4").unwrap();
+    fs::write(dir.join(".hidden"), "not adjustments").unwrap();
+    let devices = read_devices(&dir, 5);
+    fs::remove_dir_all(&dir).unwrap();
+
+    let devices = devices.unwrap();
+    let names: Vec<&str> = devices.iter().map(|device| device.name.as_str()).collect();
+    assert_eq!(names, vec!["alpha", "beta"]);
+    assert_eq!(devices[1].adjustments, vec![1, -2]);
+    assert_eq!(devices[1].start, 5);
+}
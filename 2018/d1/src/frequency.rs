@@ -1,12 +1,17 @@
-use std::collections::HashMap;
+use history::History;
+use std::collections::{HashMap, HashSet};
 use std::error;
 use std::fmt;
+use std::io::{self, BufRead};
+use std::num::ParseIntError;
 
 #[derive(Debug)]
 /// Frequency chronal wrist device is operating at.
 pub struct Frequency {
     pub value: i64,
     pub seen_values: HashMap<i64, i16>,
+    /// Every frequency passed through, only kept once `keep_history` has been called.
+    pub history: Option<History>,
 }
 
 impl Frequency {
@@ -14,77 +19,296 @@ impl Frequency {
         Frequency {
             value,
             seen_values: HashMap::new(),
+            history: None,
+        }
+    }
+
+    /// Start keeping a history of every frequency passed through from now on.
+    pub fn keep_history(&mut self) {
+        if self.history.is_none() {
+            self.history = Some(History::new(self.value));
         }
     }
 
     /// adjust_frequency works through a list of drift values and adjusts accordingly.
-    fn adjust_frequency(&mut self, adjustments: Vec<i32>) {
-        let mut result: i64 = self.value;
+    pub(crate) fn adjust_frequency(&mut self, adjustments: &[i32]) -> Result<(), AdjustmentError> {
+        self.apply(adjustments)?;
+        self.finished_pass();
+        Ok(())
+    }
+
+    /// Note the frequency reached by an adjustment in the history, when one is kept.
+    fn reached(&mut self, value: i64) {
+        if let Some(ref mut history) = self.history {
+            history.record(value);
+        }
+    }
+
+    /// Note in the history that every adjustment has been applied once more.
+    fn finished_pass(&mut self) {
+        if let Some(ref mut history) = self.history {
+            history.end_pass();
+        }
+    }
+
+    /// Apply the adjustments in turn, failing rather than going beyond what the frequency can hold.
+    /// The frequency is left at the last value reached when that happens.
+    fn apply(&mut self, adjustments: &[i32]) -> Result<(), AdjustmentError> {
         for i in adjustments {
-            result += i as i64;
+            self.value = adjusted(self.value, *i)?;
+            let value = self.value;
+            self.reached(value);
         }
-        self.value = result;
+        Ok(())
+    }
+
+    /// Cycle through the adjustments until a frequency is reached for the second time, the
+    /// frequency the device started at counting as the first time it was reached. When a repeat is
+    /// found it becomes the current value.
+    ///
+    /// A repeat can only happen between two frequencies from the first pass that differ by a
+    /// multiple of the net drift of a pass, the later pass catching up with the earlier. So once
+    /// enough passes have been made for the drift to cover the spread of the first pass, nothing
+    /// will ever repeat.
+    pub fn first_repeat(&mut self, adjustments: &[i32]) -> Result<Repeat, AdjustmentError> {
+        Ok(self
+            .first_repeat_within(adjustments, u64::MAX)?
+            .unwrap_or(Repeat::Never))
     }
 
-    /// adjust frequency recursively until the repeated frequency is found or limit_iterations
-    /// reaches 0.
-    fn adjust_frequency_until_repeats(
+    /// Look for a repeat as `first_repeat` does but give up after the number of passes. Returns
+    /// None if the search gave up before finding out whether there is one.
+    pub fn first_repeat_within(
         &mut self,
-        adjustments: &Vec<i32>,
-        mut limit_iterations: i64,
-    ) -> i64 {
-        let mut result: i64 = self.value;
+        adjustments: &[i32],
+        passes: u64,
+    ) -> Result<Option<Repeat>, AdjustmentError> {
+        let start = self.value;
+        let mut lowest = start;
+        let mut highest = start;
+        let mut result = start;
         for i in adjustments {
-            result += *i as i64;
-            let seen = self.seen_values.entry(result).or_insert(0);
-            *seen += 1;
-            if *seen > 1 {
-                println!("repeat: {}", result);
-                return 0;
+            result = adjusted(result, *i)?;
+            lowest = lowest.min(result);
+            highest = highest.max(result);
+        }
+        if adjustments.is_empty() {
+            return Ok(Some(Repeat::Never));
+        }
+        // with no drift the last adjustment of the first pass returns to the start. The spread
+        // can be wider than an i64 holds, the number of passes can't be.
+        let drift = result as i128 - start as i128;
+        let needed = if drift == 0 {
+            1
+        } else {
+            ((highest as i128 - lowest as i128) / drift.abs()) as u64 + 2
+        };
+
+        let mut result = start;
+        *self.seen_values.entry(result).or_insert(0) += 1;
+        for pass in 0..needed.min(passes) {
+            for (index, i) in adjustments.iter().enumerate() {
+                result = adjusted(result, *i)?;
+                self.reached(result);
+                let seen = self.seen_values.entry(result).or_insert(0);
+                *seen += 1;
+                if *seen > 1 {
+                    self.value = result;
+                    return Ok(Some(Repeat::Found {
+                        frequency: result,
+                        pass,
+                        index,
+                    }));
+                }
             }
+            self.finished_pass();
         }
         self.value = result;
-        if limit_iterations > 0 {
-            limit_iterations -= 1;
-            limit_iterations = self.adjust_frequency_until_repeats(adjustments, limit_iterations);
+
+        if passes < needed {
+            Ok(None)
         } else {
-            println!("no repeat found");
+            Ok(Some(Repeat::Never))
         }
-        limit_iterations
     }
 
-    /// process_adjustments takes a comma separated list of adjustments as supplied via the
-    /// command line argument and applies them to the Frequency.
-    pub fn process_adjustments(&mut self, supplied_adjustments: &str) {
-        match parse_frequency_adjustments(supplied_adjustments) {
-            Ok(results) => {
-                self.adjust_frequency(results);
-                println!("frequency {}", self.value);
+    /// Work out the first repeated frequency directly rather than cycling through the adjustments
+    /// as `first_repeat` does, which can take a very large number of passes when the drift is
+    /// small next to the spread of frequencies in a pass. When a repeat is found it becomes the
+    /// current value, no record is kept of the frequencies seen along the way and nothing is added
+    /// to the history.
+    ///
+    /// Every frequency reached in pass `p` is one from the first pass plus `p` times the drift.
+    /// Two first pass frequencies can only ever meet if they leave the same remainder when divided
+    /// by the drift, and then the one behind catches up with the one ahead of it after their
+    /// difference divided by the drift passes. Grouping the first pass by remainder and sorting
+    /// each group finds every candidate and the earliest of those is the first repeat.
+    pub fn first_repeat_by_drift(
+        &mut self,
+        adjustments: &[i32],
+    ) -> Result<Repeat, AdjustmentError> {
+        let len = adjustments.len();
+        let start = self.value;
+
+        // the frequencies reached in the first pass, any repeat within it comes first.
+        let mut reached: Vec<i64> = Vec::with_capacity(len);
+        let mut seen: HashSet<i64> = HashSet::with_capacity(len);
+        let mut result = start;
+        for (index, i) in adjustments.iter().enumerate() {
+            seen.insert(result);
+            reached.push(result);
+            result = adjusted(result, *i)?;
+            if seen.contains(&result) {
+                self.value = result;
+                return Ok(Repeat::Found {
+                    frequency: result,
+                    pass: 0,
+                    index,
+                });
             }
-            Err(fail) => {
-                println!("{}", fail);
+        }
+        // every first pass frequency lies between the start and the end of the pass, so these
+        // differences can be wider than an i64 holds.
+        let drift = result as i128 - start as i128;
+        if len == 0 || drift == 0 {
+            return Ok(Repeat::Never);
+        }
+
+        // frequency `k` of the first pass is reached again `p` passes later by adjustment
+        // `p * len + k - 1`, counting every adjustment made since the start.
+        let mut groups: HashMap<i128, Vec<(i64, usize)>> = HashMap::new();
+        for (k, frequency) in reached.iter().enumerate() {
+            groups
+                .entry((*frequency as i128).rem_euclid(drift))
+                .or_default()
+                .push((*frequency, k));
+        }
+        let mut first: Option<(u64, i64)> = None;
+        for group in groups.values_mut() {
+            group.sort();
+            for pair in group.windows(2) {
+                let (behind, ahead) = if drift > 0 {
+                    (pair[0], pair[1])
+                } else {
+                    (pair[1], pair[0])
+                };
+                let passes = ((ahead.0 as i128 - behind.0 as i128) / drift) as u64;
+                let made = passes
+                    .checked_mul(len as u64)
+                    .and_then(|made| made.checked_add(behind.1 as u64))
+                    .ok_or(AdjustmentError::Overflow { bits: 64 })?
+                    - 1;
+                if first.is_none_or(|(earliest, _)| made < earliest) {
+                    first = Some((made, ahead.0));
+                }
+            }
+        }
+
+        match first {
+            Some((made, frequency)) => {
+                self.value = frequency;
+                Ok(Repeat::Found {
+                    frequency,
+                    pass: made / len as u64,
+                    index: (made % len as u64) as usize,
+                })
             }
+            None => Ok(Repeat::Never),
         }
     }
 
-    /// Process the adjustments repetitively until either the limit is reached or the repeat is
-    /// found. This calls the recursive version of the frequency adjustment with depth limited to
-    /// the user supplied value.
+    /// process_adjustments takes a list of adjustments as supplied via the command line argument
+    /// and applies them to the Frequency, returning the resulting frequency.
+    pub fn process_adjustments(
+        &mut self,
+        supplied_adjustments: &str,
+    ) -> Result<i64, AdjustmentError> {
+        let results = parse_frequency_adjustments(supplied_adjustments)?;
+        if results.is_empty() {
+            return Err(AdjustmentError::Empty);
+        }
+        self.apply(&results)?;
+        self.finished_pass();
+        Ok(self.value)
+    }
+
+    /// Process the adjustments repetitively until either the limit on the number of passes is
+    /// reached or the first repeat is found. Only a repeat that was found is returned, not finding
+    /// one is an error.
     pub fn process_adjustments_until_repeats(
         &mut self,
         supplied_adjustments: &str,
-        limit_iterations: i64,
-    ) {
-        match parse_frequency_adjustments(supplied_adjustments) {
-            Ok(results) => {
-                self.adjust_frequency_until_repeats(&results, limit_iterations);
-                println!("frequency {}", self.value);
-            }
-            Err(fail) => {
-                println!("{}", fail);
-            }
+        passes: u64,
+    ) -> Result<Repeat, AdjustmentError> {
+        let results = parse_frequency_adjustments(supplied_adjustments)?;
+        self.repeat_within(&results, passes)
+    }
+
+    /// Apply the adjustments read from a file or stdin as each line arrives, rather than reading
+    /// them all in first. Returns the resulting frequency.
+    pub fn process_adjustments_from<R: BufRead>(
+        &mut self,
+        reader: R,
+    ) -> Result<i64, AdjustmentError> {
+        let mut applied = 0;
+        read_frequency_adjustments(reader, |results| {
+            applied += results.len();
+            self.apply(&results)
+        })?;
+        if applied == 0 {
+            return Err(AdjustmentError::Empty);
         }
+        self.finished_pass();
+        Ok(self.value)
+    }
+
+    /// Read the adjustments from a file or stdin and process them repetitively as
+    /// `process_adjustments_until_repeats` does. Every adjustment is needed for each pass so these
+    /// are kept, but only the parsed values.
+    pub fn process_adjustments_from_until_repeats<R: BufRead>(
+        &mut self,
+        reader: R,
+        passes: u64,
+    ) -> Result<Repeat, AdjustmentError> {
+        let mut adjustments: Vec<i32> = vec![];
+        read_frequency_adjustments(reader, |results| {
+            adjustments.extend(results);
+            Ok(())
+        })?;
+        self.repeat_within(&adjustments, passes)
     }
+
+    /// Look for a repeat within the number of passes, treating anything but finding one as an
+    /// error.
+    fn repeat_within(
+        &mut self,
+        adjustments: &[i32],
+        passes: u64,
+    ) -> Result<Repeat, AdjustmentError> {
+        if adjustments.is_empty() {
+            return Err(AdjustmentError::Empty);
+        }
+        match self.first_repeat_within(adjustments, passes)? {
+            Some(Repeat::Never) => Err(AdjustmentError::NoRepeat { passes: None }),
+            Some(found) => Ok(found),
+            None => Err(AdjustmentError::NoRepeat {
+                passes: Some(passes),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The outcome of cycling through the adjustments looking for a frequency reached twice.
+pub enum Repeat {
+    /// The frequency was reached again by the adjustment at `index` on `pass`, both counted from 0.
+    Found {
+        frequency: i64,
+        pass: u64,
+        index: usize,
+    },
+    /// The drift of each pass means no frequency can ever be reached twice.
+    Never,
 }
 
 #[test]
@@ -92,7 +316,7 @@ fn test_two_frequency_changes() {
     let inp: Vec<i32> = vec![1, -2];
     let mut st: Frequency = Frequency::new(0);
 
-    st.adjust_frequency(inp);
+    assert!(st.adjust_frequency(&inp).is_ok());
     assert_eq!(st.value, -1);
 }
 
@@ -101,57 +325,338 @@ fn test_three_frequency_changes() {
     let inp: Vec<i32> = vec![500, -550, 50];
     let mut st: Frequency = Frequency::new(0);
 
-    st.adjust_frequency(inp);
+    assert!(st.adjust_frequency(&inp).is_ok());
     assert_eq!(st.value, 0);
 }
 
-/// parse_frequency_adjustments from a comma separated string of values into an array..
-fn parse_frequency_adjustments(input: &str) -> Result<Vec<i32>, AdjustmentError> {
+#[test]
+fn test_first_repeat_examples() {
+    let examples: Vec<(Vec<i32>, i64)> = vec![
+        (vec![1, -1], 0),
+        (vec![3, 3, 4, -2, -4], 10),
+        (vec![-6, 3, 8, 5, -6], 5),
+        (vec![7, 7, -2, -7, -4], 14),
+    ];
+    for (adjustments, expected) in examples {
+        match Frequency::new(0).first_repeat(&adjustments).unwrap() {
+            Repeat::Found { frequency, .. } => assert_eq!(frequency, expected),
+            Repeat::Never => panic!("expected {} to repeat", expected),
+        }
+    }
+}
+
+#[test]
+fn test_first_repeat_position() {
+    let mut freq = Frequency::new(0);
+    assert_eq!(
+        freq.first_repeat(&[3, 3, 4, -2, -4]).unwrap(),
+        Repeat::Found {
+            frequency: 10,
+            pass: 1,
+            index: 1
+        }
+    );
+    assert_eq!(freq.value, 10);
+}
+
+#[test]
+fn test_first_repeat_never() {
+    assert_eq!(
+        Frequency::new(0).first_repeat(&[1, 2, 3]).unwrap(),
+        Repeat::Never
+    );
+    assert_eq!(Frequency::new(0).first_repeat(&[]).unwrap(), Repeat::Never);
+    // -2 and +1 are apart by a multiple of the drift, so they do meet eventually.
+    assert_ne!(
+        Frequency::new(0).first_repeat(&[-2, 3, 2]).unwrap(),
+        Repeat::Never
+    );
+}
+
+#[test]
+fn test_first_repeat_by_drift_examples() {
+    let examples: Vec<(Vec<i32>, Repeat)> = vec![
+        (
+            vec![1, -1],
+            Repeat::Found {
+                frequency: 0,
+                pass: 0,
+                index: 1,
+            },
+        ),
+        (
+            vec![3, 3, 4, -2, -4],
+            Repeat::Found {
+                frequency: 10,
+                pass: 1,
+                index: 1,
+            },
+        ),
+        (
+            vec![-6, 3, 8, 5, -6],
+            Repeat::Found {
+                frequency: 5,
+                pass: 2,
+                index: 1,
+            },
+        ),
+        (vec![1, 2, 3], Repeat::Never),
+        (vec![], Repeat::Never),
+    ];
+    for (adjustments, expected) in examples {
+        assert_eq!(
+            Frequency::new(0)
+                .first_repeat_by_drift(&adjustments)
+                .unwrap(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_first_repeat_within_gives_up() {
+    assert_eq!(
+        Frequency::new(0)
+            .first_repeat_within(&[1000, -999], 5)
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_first_repeat_overflow() {
+    // the first adjustment already goes past the largest frequency.
+    let adjustments = [1, -2];
+    match Frequency::new(i64::MAX).first_repeat(&adjustments) {
+        Err(AdjustmentError::Overflow { bits: 64 }) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+    match Frequency::new(i64::MAX).first_repeat_by_drift(&adjustments) {
+        Err(AdjustmentError::Overflow { bits: 64 }) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+    // reaching the largest frequency is fine, drifting back down from it to a repeat.
+    for repeat in &[
+        Frequency::new(i64::MAX - 3).first_repeat(&[3, -4]),
+        Frequency::new(i64::MAX - 3).first_repeat_by_drift(&[3, -4]),
+    ] {
+        assert_eq!(
+            repeat.as_ref().unwrap(),
+            &Repeat::Found {
+                frequency: i64::MAX - 3,
+                pass: 3,
+                index: 0,
+            }
+        );
+    }
+}
+
+/// Apply a single adjustment to a frequency, failing rather than going beyond what an i64 can
+/// hold.
+fn adjusted(value: i64, adjustment: i32) -> Result<i64, AdjustmentError> {
+    value
+        .checked_add(adjustment as i64)
+        .ok_or(AdjustmentError::Overflow { bits: 64 })
+}
+
+/// parse_frequency_adjustments from a string of values into an array. The values may be separated
+/// by commas, whitespace or newlines and may carry an explicit `+` sign.
+pub fn parse_frequency_adjustments(input: &str) -> Result<Vec<i32>, AdjustmentError> {
     let mut results: Vec<i32> = vec![];
-    let values: Vec<&str> = input.split(",").collect();
+    let mut position = 0;
+    for (index, line) in input.lines().enumerate() {
+        parse_line(line, index + 1, &mut position, &mut results)?;
+    }
+    Ok(results)
+}
+
+/// Read the adjustments a line at a time, handing those found on each line to `apply` as soon as
+/// the line is parsed. Stops at the first line that can't be read or parsed, or at the first
+/// error from `apply`.
+pub(crate) fn read_frequency_adjustments<R, F>(
+    reader: R,
+    mut apply: F,
+) -> Result<(), AdjustmentError>
+where
+    R: BufRead,
+    F: FnMut(Vec<i32>) -> Result<(), AdjustmentError>,
+{
+    let mut position = 0;
+    for (index, line) in reader.lines().enumerate() {
+        let mut results: Vec<i32> = vec![];
+        parse_line(&line?, index + 1, &mut position, &mut results)?;
+        apply(results)?;
+    }
+    Ok(())
+}
+
+/// Parse the values on a line onto the end of the results. `position` counts the values read so
+/// far, across every line.
+pub(crate) fn parse_line(
+    line: &str,
+    line_number: usize,
+    position: &mut usize,
+    results: &mut Vec<i32>,
+) -> Result<(), AdjustmentError> {
+    let values = line
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|val| !val.is_empty());
 
     for val in values {
-        let v = val.parse::<i32>();
-        if v.is_err() {
-            return Err(AdjustmentError::new(val, "could not parse as i32"));
+        *position += 1;
+        match val.parse::<i32>() {
+            Ok(v) => results.push(v),
+            Err(err) => {
+                return Err(AdjustmentError::Parse {
+                    line: line_number,
+                    position: *position,
+                    value: val.to_string(),
+                    err,
+                })
+            }
         }
-        results.push(v.unwrap());
     }
+    Ok(())
+}
 
-    return Ok(results);
+#[test]
+fn test_parse_mixed_separators() {
+    let results = parse_frequency_adjustments("+1, -2\n+3\t-4  +5,,").unwrap();
+    assert_eq!(results, vec![1, -2, 3, -4, 5]);
 }
 
-#[derive(Debug, Clone)]
-/// A base error to return to identify that there has been a condition found that does not allow us
-/// to continue processing.
-struct AdjustmentError<'a> {
-    source: &'a str,
-    message: &'a str,
+#[test]
+fn test_read_adjustments_by_line() {
+    let mut lines: Vec<Vec<i32>> = vec![];
+    read_frequency_adjustments("+7\n-3, +2\n\n-1\n".as_bytes(), |results| {
+        lines.push(results);
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(lines, vec![vec![7], vec![-3, 2], vec![], vec![-1]]);
+
+    let err = read_frequency_adjustments("+7\n-3, seven\n".as_bytes(), |_| Ok(())).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 2, adjustment 3: couldn't read \"seven\", invalid digit found in string"
+    );
 }
 
-impl<'a> AdjustmentError<'a> {
-    pub fn new(source: &'a str, message: &'a str) -> AdjustmentError<'a> {
-        AdjustmentError { source, message }
-    }
+#[derive(Debug)]
+/// Something that stopped the adjustments from being applied.
+pub enum AdjustmentError {
+    /// A value couldn't be read as an adjustment. The line and the position of the value among all
+    /// the adjustments are both counted from 1.
+    Parse {
+        line: usize,
+        position: usize,
+        value: String,
+        err: ParseIntError,
+    },
+    /// The frequency, or the number of adjustments needed to find a repeat, went beyond the
+    /// largest or smallest value an integer of this many bits can hold.
+    Overflow { bits: u32 },
+    /// There were no adjustments to apply.
+    Empty,
+    /// No frequency was reached twice. Holds the number of passes made when the search gave up
+    /// before finding out, or None when no frequency can ever be reached twice.
+    NoRepeat { passes: Option<u64> },
+    /// The adjustments couldn't be read from the file or stdin.
+    Read(io::Error),
+}
 
-    fn reason(&self) -> String {
-        format!("{}: {}", self.message, self.source)
+impl From<io::Error> for AdjustmentError {
+    fn from(err: io::Error) -> AdjustmentError {
+        AdjustmentError::Read(err)
     }
 }
 
-impl<'a> fmt::Display for AdjustmentError<'a> {
+impl fmt::Display for AdjustmentError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "failed to process adjustment: {}", self.reason())
+        match self {
+            AdjustmentError::Parse {
+                line,
+                position,
+                value,
+                err,
+            } => write!(
+                f,
+                "line {}, adjustment {}: couldn't read {:?}, {}",
+                line, position, value, err
+            ),
+            AdjustmentError::Overflow { bits } => write!(
+                f,
+                "the frequency went beyond what a {} bit integer can hold",
+                bits
+            ),
+            AdjustmentError::Empty => write!(f, "there are no adjustments to apply"),
+            AdjustmentError::NoRepeat { passes: None } => write!(f, "the frequency never repeats"),
+            AdjustmentError::NoRepeat {
+                passes: Some(passes),
+            } => write!(f, "no repeat found within {} passes", passes),
+            AdjustmentError::Read(err) => write!(f, "failed to read adjustments: {}", err),
+        }
     }
 }
 
-impl<'a> error::Error for AdjustmentError<'a> {
-    fn description(&self) -> &str {
-        "failed to process adjustment"
+impl error::Error for AdjustmentError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            AdjustmentError::Parse { err, .. } => Some(err),
+            AdjustmentError::Read(err) => Some(err),
+            _ => None,
+        }
     }
+}
 
-    fn cause(&self) -> Option<&error::Error> {
-        // Generic error, underlying cause isn't tracked.
-        None
+#[test]
+fn test_process_adjustments_errors() {
+    use std::error::Error;
+
+    let err = Frequency::new(0)
+        .process_adjustments("+1, +2, x")
+        .unwrap_err();
+    match err {
+        AdjustmentError::Parse { line, position, .. } => assert_eq!((line, position), (1, 3)),
+        _ => panic!("unexpected error {:?}", err),
+    }
+    assert!(err.source().is_some());
+
+    match Frequency::new(0).process_adjustments(" , ") {
+        Err(AdjustmentError::Empty) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+    match Frequency::new(i64::MAX).process_adjustments("+1") {
+        Err(AdjustmentError::Overflow { bits: 64 }) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+    match Frequency::new(0).process_adjustments_until_repeats("+1, +2", 10) {
+        Err(AdjustmentError::NoRepeat { passes: None }) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+    match Frequency::new(0).process_adjustments_from_until_repeats("+1000\n-999".as_bytes(), 5) {
+        Err(AdjustmentError::NoRepeat { passes: Some(5) }) => {}
+        other => panic!("unexpected result {:?}", other),
     }
 }
+
+#[test]
+fn test_history_across_passes() {
+    let mut freq = Frequency::new(0);
+    freq.keep_history();
+    freq.first_repeat(&[3, 3, 4, -2, -4]).unwrap();
+    let history = freq.history.unwrap();
+    assert_eq!(history.values(), &[0, 3, 6, 10, 8, 4, 7, 10]);
+    assert_eq!(history.report().drift_per_pass, vec![4]);
+}
+
+#[test]
+fn test_process_adjustments_from() {
+    let mut freq = Frequency::new(5);
+    assert_eq!(
+        freq.process_adjustments_from("+1\n-3, +4\n".as_bytes())
+            .unwrap(),
+        7
+    );
+    assert_eq!(freq.value, 7);
+}
@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// Number of the most visited frequencies included in a report.
+const MOST_VISITED: usize = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Every frequency a device passed through, in order, kept to check a set of calibration
+/// adjustments behaves as expected. This can grow very large when looking for a repeat over many
+/// passes so it is only kept when asked for.
+pub struct History {
+    values: Vec<i64>,
+    /// The number of values recorded when each pass through the adjustments finished.
+    pass_ends: Vec<usize>,
+}
+
+impl History {
+    /// Start a history at the frequency the device is currently on.
+    pub fn new(start: i64) -> History {
+        History {
+            values: vec![start],
+            pass_ends: vec![],
+        }
+    }
+
+    /// Record the frequency reached by the next adjustment.
+    pub fn record(&mut self, value: i64) {
+        self.values.push(value);
+    }
+
+    /// Note that every adjustment has been applied once more.
+    pub fn end_pass(&mut self) {
+        self.pass_ends.push(self.values.len());
+    }
+
+    /// Every frequency recorded, starting with the one the device started on.
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+
+    /// Summarise the frequencies passed through.
+    pub fn report(&self) -> Report {
+        let mut visits: HashMap<i64, usize> = HashMap::new();
+        for value in &self.values {
+            *visits.entry(*value).or_insert(0) += 1;
+        }
+        let mut most_visited: Vec<(i64, usize)> = visits.iter().map(|(v, n)| (*v, *n)).collect();
+        most_visited.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        most_visited.truncate(MOST_VISITED);
+
+        // each pass starts from wherever the previous one finished.
+        let mut drift_per_pass = vec![];
+        let mut pass_start = self.values[0];
+        for end in &self.pass_ends {
+            let pass_end = self.values[end - 1];
+            drift_per_pass.push(pass_end - pass_start);
+            pass_start = pass_end;
+        }
+
+        Report {
+            steps: self.values.len() - 1,
+            lowest: *self.values.iter().min().unwrap_or(&0),
+            highest: *self.values.iter().max().unwrap_or(&0),
+            drift_per_pass,
+            distinct: visits.len(),
+            most_visited,
+        }
+    }
+
+    /// The frequencies as comma separated values, a line for each step with the pass it was part
+    /// of. The starting frequency is step 0 of pass 0 and the passes are counted from 1.
+    pub fn csv(&self) -> String {
+        let mut csv = String::from("step,pass,frequency\n");
+        let mut pass = 0;
+        for (step, value) in self.values.iter().enumerate() {
+            // a pass that finished on the previous step means this one starts the next.
+            while step > 0 && pass < self.pass_ends.len() && step >= self.pass_ends[pass] {
+                pass += 1;
+            }
+            let this_pass = if step == 0 { 0 } else { pass + 1 };
+            csv.push_str(&format!("{},{},{}\n", step, this_pass, value));
+        }
+        csv
+    }
+
+    /// A rough plot of the frequencies over time, squeezed into the width and height given in
+    /// characters. Each column covers an equal share of the steps and is marked at the frequency
+    /// reached at the end of them.
+    pub fn ascii(&self, width: usize, height: usize) -> String {
+        let report = self.report();
+        let (lowest, highest) = (report.lowest, report.highest);
+        let width = width.clamp(1, self.values.len());
+        let height = height.max(2);
+        let spread = (highest - lowest).max(1) as f64;
+
+        let mut rows = vec![vec![' '; width]; height];
+        for (col, row) in (0..width).map(|col| {
+            let step = (col + 1) * self.values.len() / width - 1;
+            let level = (self.values[step] - lowest) as f64 / spread;
+            (
+                col,
+                height - 1 - (level * (height - 1) as f64).round() as usize,
+            )
+        }) {
+            rows[row][col] = '*';
+        }
+
+        let label_width = format!("{}", highest)
+            .len()
+            .max(format!("{}", lowest).len());
+        let mut plot = String::new();
+        for (index, row) in rows.iter().enumerate() {
+            let label = if index == 0 {
+                format!("{}", highest)
+            } else if index == height - 1 {
+                format!("{}", lowest)
+            } else {
+                String::new()
+            };
+            let line: String = row.iter().collect();
+            plot.push_str(&format!(
+                "{:>width$} |{}\n",
+                label,
+                line.trim_end(),
+                width = label_width
+            ));
+        }
+        plot
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Summary of the frequencies a device passed through.
+pub struct Report {
+    /// The number of adjustments applied.
+    pub steps: usize,
+    pub lowest: i64,
+    pub highest: i64,
+    /// How far the frequency moved over each complete pass through the adjustments.
+    pub drift_per_pass: Vec<i64>,
+    /// The number of different frequencies reached, including the start.
+    pub distinct: usize,
+    /// The frequencies reached most often and how often, the most visited first.
+    pub most_visited: Vec<(i64, usize)>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let drift: Vec<String> = self.drift_per_pass.iter().map(|d| d.to_string()).collect();
+        let visited: Vec<String> = self
+            .most_visited
+            .iter()
+            .map(|(value, visits)| format!("{} ({})", value, visits))
+            .collect();
+
+        writeln!(f, "steps: {}", self.steps)?;
+        writeln!(f, "lowest: {}", self.lowest)?;
+        writeln!(f, "highest: {}", self.highest)?;
+        if drift.is_empty() {
+            writeln!(f, "drift per pass: none, no pass was completed")?;
+        } else {
+            writeln!(f, "drift per pass: {}", drift.join(", "))?;
+        }
+        writeln!(f, "distinct frequencies: {}", self.distinct)?;
+        writeln!(f, "most visited: {}", visited.join(", "))
+    }
+}
+
+#[cfg(test)]
+fn example() -> History {
+    // two passes of +3, +3, +4, -2, -4 then the start of a third.
+    let mut history = History::new(0);
+    for value in &[3, 6, 10, 8, 4] {
+        history.record(*value);
+    }
+    history.end_pass();
+    for value in &[7, 10, 14, 12, 8] {
+        history.record(*value);
+    }
+    history.end_pass();
+    history.record(11);
+    history
+}
+
+#[test]
+fn test_report() {
+    let report = example().report();
+    assert_eq!(report.steps, 11);
+    assert_eq!((report.lowest, report.highest), (0, 14));
+    assert_eq!(report.drift_per_pass, vec![4, 4]);
+    assert_eq!(report.distinct, 10);
+    assert_eq!(report.most_visited[0], (8, 2));
+    assert_eq!(report.most_visited[1], (10, 2));
+    assert_eq!(report.most_visited[2], (0, 1));
+}
+
+#[test]
+fn test_csv() {
+    let csv = example().csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "step,pass,frequency");
+    assert_eq!(lines[1], "0,0,0");
+    assert_eq!(lines[2], "1,1,3");
+    assert_eq!(lines[6], "5,1,4");
+    assert_eq!(lines[7], "6,2,7");
+    assert_eq!(lines[12], "11,3,11");
+}
+
+#[test]
+fn test_ascii() {
+    let mut history = History::new(0);
+    for value in &[1, 2, 3, 2, 1] {
+        history.record(*value);
+    }
+    assert_eq!(history.ascii(6, 4), "3 |   *\n  |  * *\n  | *   *\n0 |*\n");
+}
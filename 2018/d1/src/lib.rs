@@ -0,0 +1,93 @@
+extern crate common;
+extern crate num_bigint;
+
+pub mod devices;
+pub mod frequency;
+pub mod history;
+pub mod width;
+
+use common::generate::{Generate, Rng};
+use common::input::Input;
+use common::parse::{parse_lines, ParseError};
+use common::solution::{Answer, Solution};
+use frequency::{Frequency, Repeat};
+
+/// Day 1: Chronal Calibration.
+pub struct ChronalCalibration;
+
+impl Solution for ChronalCalibration {
+    type Parsed<'a> = Vec<i32>;
+
+    /// The puzzle input lists one adjustment per line, such as `+7` or `-3`.
+    fn parse(input: &Input) -> Result<Vec<i32>, ParseError> {
+        parse_lines(input.as_str(), |line| {
+            line.parse::<i32>()
+                .map_err(|err| ParseError::new(line, format!("bad adjustment, {}", err)))
+        })
+    }
+
+    /// The resulting frequency after applying every adjustment once.
+    fn part1(adjustments: &Vec<i32>) -> Answer {
+        let mut freq = Frequency::new(0);
+        match freq.adjust_frequency(adjustments) {
+            Ok(()) => freq.value.into(),
+            Err(err) => Answer::Failed(err.to_string()),
+        }
+    }
+
+    /// The first frequency reached twice when cycling through the adjustments.
+    fn part2(adjustments: &Vec<i32>) -> Answer {
+        match Frequency::new(0).first_repeat_by_drift(adjustments) {
+            Ok(Repeat::Found { frequency, .. }) => frequency.into(),
+            Ok(Repeat::Never) => Answer::Unsolved,
+            Err(err) => Answer::Failed(err.to_string()),
+        }
+    }
+}
+
+impl Generate for ChronalCalibration {
+    /// Inputs for the puzzle hold around a thousand adjustments.
+    const REAL_SIZE: usize = 1000;
+
+    /// Adjustments of up to 20 either way, each as likely to raise the frequency as lower it.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            let mut adjustment = rng.between(1, 20);
+            if rng.one_in(2) {
+                adjustment = -adjustment;
+            }
+            input.push_str(&format!("{:+}\n", adjustment));
+        }
+        input
+    }
+}
+
+#[test]
+fn test_generated_input_parses() {
+    use common::generate::{generate, Size};
+
+    let input = Input::new(&generate::<ChronalCalibration>(Size::Records(50), 1));
+    assert_eq!(ChronalCalibration::parse(&input).unwrap().len(), 50);
+}
+
+#[test]
+fn test_first_repeat_by_drift_matches_cycling() {
+    use common::generate::{generate, Size};
+
+    for seed in 0..50 {
+        let input = Input::new(&generate::<ChronalCalibration>(Size::Records(100), seed));
+        let adjustments = ChronalCalibration::parse(&input).unwrap();
+        for start in &[0, 25, -400] {
+            assert_eq!(
+                Frequency::new(*start)
+                    .first_repeat_by_drift(&adjustments)
+                    .unwrap(),
+                Frequency::new(*start).first_repeat(&adjustments).unwrap(),
+                "seed {} starting at {}",
+                seed,
+                start
+            );
+        }
+    }
+}
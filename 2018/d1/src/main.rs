@@ -1,59 +1,347 @@
 extern crate clap;
-use clap::{App, Arg};
+extern crate common;
+extern crate d1;
+use clap::{value_t, App, AppSettings, Arg, ArgGroup, ArgMatches, Shell, SubCommand};
+use common::input::STDIN_ARG;
+use d1::devices;
+use d1::frequency::{self, AdjustmentError, Frequency};
+use d1::width::Width;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use std::process;
 
-pub mod frequency;
+/// Size of the plot of frequencies in characters.
+const PLOT_WIDTH: usize = 72;
+const PLOT_HEIGHT: usize = 16;
 
 fn main() {
-    // set up the command line args
-    let matches = App::new("p1")
+    let matches = app().get_matches();
+
+    let succeeded = match matches.subcommand() {
+        ("sum", Some(sum_matches)) => sum(sum_matches),
+        ("first-repeat", Some(repeat_matches)) => first_repeat(repeat_matches),
+        ("stats", Some(stats_matches)) => stats(stats_matches),
+        ("devices", Some(devices_matches)) => calibrate_devices(devices_matches),
+        ("completions", Some(completions_matches)) => completions(completions_matches),
+        _ => true,
+    };
+    if !succeeded {
+        process::exit(1);
+    }
+}
+
+fn app<'a, 'b>() -> App<'a, 'b> {
+    App::new("d1")
         .version("1.0")
         .author("Jonathan Pulfer")
         .about("First puzzle solver for Advent Of Code 2018")
-        .arg(
-            Arg::with_name("start_frequency")
-                .short("sf")
-                .long("start_frequency")
-                .value_name("START")
-                .help("The initial frequency of the Chronal device. Defaults to 0")
-                .takes_value(true),
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            adjustment_args(
+                SubCommand::with_name("sum")
+                    .about("Apply every adjustment once and print the resulting frequency"),
+            )
+            .arg(
+                Arg::with_name("width")
+                    .short("w")
+                    .long("width")
+                    .value_name("BITS")
+                    .help("Sum the frequency in a wider integer, or one without a limit, when it would overflow 64 bits")
+                    .possible_values(Width::NAMES)
+                    .default_value("64")
+                    .takes_value(true),
+            ),
         )
+        .subcommand(passes_arg(adjustment_args(
+            SubCommand::with_name("first-repeat")
+                .about("Cycle through the adjustments until a frequency is reached twice"),
+        )))
+        .subcommand(
+            passes_arg(adjustment_args(
+                SubCommand::with_name("stats").about(
+                    "Report on every frequency passed through, such as the lowest, highest and most visited",
+                ),
+            ))
+            .arg(
+                Arg::with_name("first_repeat")
+                    .short("r")
+                    .long("first-repeat")
+                    .help("Follow the adjustments until the first repeat rather than for a single pass"),
+            )
+            .arg(
+                Arg::with_name("series")
+                    .long("series")
+                    .value_name("FORMAT")
+                    .help("Also print every frequency passed through as a rough plot or as comma separated values")
+                    .possible_values(&["ascii", "csv"])
+                    .takes_value(true),
+            ),
+        )
+        .subcommand(
+            start_arg(
+                SubCommand::with_name("devices")
+                    .about("Calibrate several devices at once and print a table of how each got on"),
+            )
+            .arg(
+                Arg::with_name("path")
+                    .value_name("PATH")
+                    .help("A directory with a file of adjustments for each device, or a file split into sections headed [name] or [name start=N]. Use - to read the sections from stdin")
+                    .required(true)
+                    .index(1),
+            )
+            .arg(
+                Arg::with_name("device_start")
+                    .long("device-start")
+                    .value_name("NAME=START")
+                    .help("The initial frequency of a single device, overriding --start-frequency and any start in its header")
+                    .multiple(true)
+                    .number_of_values(1)
+                    .takes_value(true),
+            ),
+        )
+        .subcommand(
+            SubCommand::with_name("completions")
+                .about("Write a completion script for the shell to stdout")
+                .arg(
+                    Arg::with_name("shell")
+                        .value_name("SHELL")
+                        .possible_values(&Shell::variants())
+                        .required(true)
+                        .index(1),
+                ),
+        )
+}
+
+/// Add the argument for the frequency a device starts at.
+fn start_arg<'a, 'b>(cmd: App<'a, 'b>) -> App<'a, 'b> {
+    cmd.arg(
+        Arg::with_name("start_frequency")
+            .short("s")
+            .long("start-frequency")
+            .value_name("START")
+            .help("The initial frequency of the Chronal device")
+            .allow_hyphen_values(true)
+            .default_value("0")
+            .takes_value(true),
+    )
+}
+
+/// Add the arguments for where the adjustments of a single device come from.
+fn adjustment_args<'a, 'b>(cmd: App<'a, 'b>) -> App<'a, 'b> {
+    start_arg(cmd)
         .arg(
             Arg::with_name("adjustments")
-                .short("adj")
+                .short("a")
                 .long("adjustments")
                 .value_name("ADJUSTMENTS")
-                .help("Comma separated list of adjustments to make to the frequency")
+                .help("Comma or space separated list of adjustments to make to the frequency")
+                .allow_hyphen_values(true)
                 .takes_value(true),
         )
         .arg(
-            Arg::with_name("repeats")
-                .short("r")
-                .long("repeats")
-                .value_name("REPEATS")
-                .help("number of times to process adjustments to look for repeating frequency.")
+            Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .value_name("PATH")
+                .help("Read the adjustments from this file instead, or - for stdin. They may be separated by newlines, commas or spaces")
                 .takes_value(true),
         )
-        .get_matches();
+        .group(
+            ArgGroup::with_name("source")
+                .args(&["adjustments", "input"])
+                .required(true),
+        )
+}
 
-    // extract the starting frequency and convert to i64.
-    let rcvd_freq: i64 = matches
-        .value_of("start_frequency")
-        .unwrap_or("0")
-        .parse()
-        .unwrap();
+/// Add the argument limiting how many passes are made looking for a repeat.
+fn passes_arg<'a, 'b>(cmd: App<'a, 'b>) -> App<'a, 'b> {
+    cmd.arg(
+        Arg::with_name("passes")
+            .short("p")
+            .long("passes")
+            .value_name("PASSES")
+            .help("Give up looking for a repeat after this many passes through the adjustments. There is no limit when left out")
+            .takes_value(true),
+    )
+}
 
-    // extract the number of repeats and convert to i64.
-    let rcvd_repeats: i64 = matches.value_of("repeats").unwrap_or("0").parse().unwrap();
+/// The frequency picked by the argument added with `start_arg`.
+fn start_frequency(matches: &ArgMatches) -> i64 {
+    value_t!(matches, "start_frequency", i64).unwrap_or_else(|e| e.exit())
+}
 
-    // extract the adjustments.
-    let adj = matches.value_of("adjustments").unwrap_or("0");
+/// The limit picked by the argument added with `passes_arg`.
+fn passes(matches: &ArgMatches) -> u64 {
+    if matches.is_present("passes") {
+        value_t!(matches, "passes", u64).unwrap_or_else(|e| e.exit())
+    } else {
+        u64::MAX
+    }
+}
 
-    // initialise the Frequency ready for adjustment.
-    let mut freq: frequency::Frequency = frequency::Frequency::new(rcvd_freq);
+/// The adjustments picked by the arguments added with `adjustment_args`, ready to be read a line
+/// at a time as they are applied.
+fn adjustments<'a>(matches: &'a ArgMatches) -> Result<Box<dyn BufRead + 'a>, String> {
+    match matches.value_of("input") {
+        Some(STDIN_ARG) => Ok(Box::new(io::stdin().lock())),
+        Some(path) => File::open(path)
+            .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+            .map_err(|err| format!("couldn't open {}: {}", path, err)),
+        None => Ok(Box::new(
+            matches
+                .value_of("adjustments")
+                .unwrap_or_default()
+                .as_bytes(),
+        )),
+    }
+}
 
-    if rcvd_repeats > 0 {
-        freq.process_adjustments_until_repeats(adj, rcvd_repeats);
+fn sum(matches: &ArgMatches) -> bool {
+    let width = value_t!(matches, "width", Width).unwrap_or_else(|e| e.exit());
+    let start = start_frequency(matches);
+    let reader = match adjustments(matches) {
+        Ok(reader) => reader,
+        Err(err) => return failed(err),
+    };
+    match width.sum(start, reader) {
+        Err(err @ AdjustmentError::Overflow { bits: 64 }) => failed(format!(
+            "{}\ntry summing it with --width 128 or --width big",
+            err
+        )),
+        outcome => report(outcome.map(describe_value)),
+    }
+}
+
+fn first_repeat(matches: &ArgMatches) -> bool {
+    let mut freq = Frequency::new(start_frequency(matches));
+    let passes = passes(matches);
+    let reader = match adjustments(matches) {
+        Ok(reader) => reader,
+        Err(err) => return failed(err),
+    };
+    report(
+        freq.process_adjustments_from_until_repeats(reader, passes)
+            .map(describe_repeat),
+    )
+}
+
+fn stats(matches: &ArgMatches) -> bool {
+    let mut freq = Frequency::new(start_frequency(matches));
+    freq.keep_history();
+    let passes = passes(matches);
+    let reader = match adjustments(matches) {
+        Ok(reader) => reader,
+        Err(err) => return failed(err),
+    };
+    let outcome = if matches.is_present("first_repeat") {
+        freq.process_adjustments_from_until_repeats(reader, passes)
+            .map(describe_repeat)
+    } else {
+        freq.process_adjustments_from(reader).map(describe_value)
+    };
+    if !report(outcome) {
+        return false;
+    }
+
+    if let Some(ref history) = freq.history {
+        print!("{}", history.report());
+        match matches.value_of("series") {
+            Some("csv") => print!("{}", history.csv()),
+            Some(_) => print!("{}", history.ascii(PLOT_WIDTH, PLOT_HEIGHT)),
+            None => {}
+        }
+    }
+    true
+}
+
+/// Calibrate every device found at the path and print a table of how each got on. Returns false
+/// when any couldn't be calibrated.
+fn calibrate_devices(matches: &ArgMatches) -> bool {
+    let default_start = start_frequency(matches);
+    let path = matches.value_of("path").unwrap_or(STDIN_ARG);
+    let read = if path == STDIN_ARG {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|err| err.to_string())
+            .and_then(|_| devices::parse_devices(&input, default_start).map_err(|e| e.to_string()))
+    } else if Path::new(path).is_dir() {
+        devices::read_devices(path, default_start).map_err(|err| err.to_string())
     } else {
-        freq.process_adjustments(adj);
+        fs::read_to_string(path)
+            .map_err(|err| format!("couldn't open {}: {}", path, err))
+            .and_then(|input| {
+                devices::parse_devices(&input, default_start).map_err(|err| err.to_string())
+            })
+    };
+    let mut found = match read {
+        Ok(found) => found,
+        Err(err) => return failed(err),
+    };
+
+    for start in matches.values_of("device_start").into_iter().flatten() {
+        let mut parts = start.splitn(2, '=');
+        let name = parts.next().unwrap_or_default();
+        let value = parts.next().and_then(|value| value.parse().ok());
+        match (found.iter_mut().find(|device| device.name == name), value) {
+            (Some(device), Some(value)) => device.start = value,
+            (None, Some(_)) => return failed(format!("there is no device named {:?}", name)),
+            (_, None) => {
+                return failed(format!(
+                    "expected --device-start NAME=START, found {:?}",
+                    start
+                ))
+            }
+        }
+    }
+
+    let calibrations = devices::calibrate(&found);
+    print!("{}", devices::table(&calibrations));
+    calibrations
+        .iter()
+        .all(|calibration| calibration.outcome.is_ok())
+}
+
+fn completions(matches: &ArgMatches) -> bool {
+    let shell = value_t!(matches, "shell", Shell).unwrap_or_else(|e| e.exit());
+    app().gen_completions_to("d1", shell, &mut io::stdout());
+    true
+}
+
+/// Print the outcome, returning whether it succeeded.
+fn report(outcome: Result<String, AdjustmentError>) -> bool {
+    match outcome {
+        Ok(report) => {
+            println!("{}", report);
+            true
+        }
+        Err(err) => failed(err),
+    }
+}
+
+fn failed<E: fmt::Display>(err: E) -> bool {
+    eprintln!("{}", err);
+    false
+}
+
+fn describe_value<V: fmt::Display>(value: V) -> String {
+    format!("frequency {}", value)
+}
+
+fn describe_repeat(repeat: frequency::Repeat) -> String {
+    match repeat {
+        frequency::Repeat::Found {
+            frequency,
+            pass,
+            index,
+        } => format!(
+            "frequency {} (pass {}, adjustment {})",
+            frequency,
+            pass + 1,
+            index + 1
+        ),
+        frequency::Repeat::Never => String::from("the frequency never repeats"),
     }
 }
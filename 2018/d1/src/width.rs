@@ -0,0 +1,151 @@
+use frequency::{read_frequency_adjustments, AdjustmentError};
+use num_bigint::BigInt;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// A number a frequency can be summed in. Wider numbers take longer to add up but can follow a
+/// frequency much further than an i64 before overflowing, or never overflow at all.
+pub trait Accumulator: Sized + fmt::Display {
+    /// The number of bits the frequency is held in, reported when it overflows.
+    const BITS: u32;
+
+    /// The frequency the device starts at.
+    fn start(value: i64) -> Self;
+
+    /// The frequency after the adjustment, or None when it can't be held.
+    fn adjusted(self, adjustment: i32) -> Option<Self>;
+}
+
+impl Accumulator for i64 {
+    const BITS: u32 = 64;
+
+    fn start(value: i64) -> i64 {
+        value
+    }
+
+    fn adjusted(self, adjustment: i32) -> Option<i64> {
+        self.checked_add(adjustment as i64)
+    }
+}
+
+impl Accumulator for i128 {
+    const BITS: u32 = 128;
+
+    fn start(value: i64) -> i128 {
+        value as i128
+    }
+
+    fn adjusted(self, adjustment: i32) -> Option<i128> {
+        self.checked_add(adjustment as i128)
+    }
+}
+
+impl Accumulator for BigInt {
+    /// Never overflows, so never reported.
+    const BITS: u32 = 0;
+
+    fn start(value: i64) -> BigInt {
+        BigInt::from(value)
+    }
+
+    fn adjusted(self, adjustment: i32) -> Option<BigInt> {
+        Some(self + adjustment)
+    }
+}
+
+/// Sum the adjustments read a line at a time onto the starting frequency, held in whichever
+/// accumulator is asked for.
+pub fn sum_adjustments<A, R>(start: i64, reader: R) -> Result<A, AdjustmentError>
+where
+    A: Accumulator,
+    R: BufRead,
+{
+    let mut value = Some(A::start(start));
+    let mut applied = 0;
+    read_frequency_adjustments(reader, |results| {
+        applied += results.len();
+        for adjustment in results {
+            value = value.take().and_then(|value| value.adjusted(adjustment));
+            if value.is_none() {
+                return Err(AdjustmentError::Overflow { bits: A::BITS });
+            }
+        }
+        Ok(())
+    })?;
+    if applied == 0 {
+        return Err(AdjustmentError::Empty);
+    }
+    value.ok_or(AdjustmentError::Overflow { bits: A::BITS })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// How wide a number the frequency is summed in.
+pub enum Width {
+    Bits64,
+    Bits128,
+    /// As many bits as it takes.
+    Big,
+}
+
+impl Width {
+    pub const NAMES: &'static [&'static str] = &["64", "128", "big"];
+
+    /// Sum the adjustments as `sum_adjustments` does in a number of this width, returning the
+    /// resulting frequency written out.
+    pub fn sum<R: BufRead>(self, start: i64, reader: R) -> Result<String, AdjustmentError> {
+        match self {
+            Width::Bits64 => sum_adjustments::<i64, R>(start, reader).map(|v| v.to_string()),
+            Width::Bits128 => sum_adjustments::<i128, R>(start, reader).map(|v| v.to_string()),
+            Width::Big => sum_adjustments::<BigInt, R>(start, reader).map(|v| v.to_string()),
+        }
+    }
+}
+
+impl FromStr for Width {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Width, String> {
+        match s {
+            "64" => Ok(Width::Bits64),
+            "128" => Ok(Width::Bits128),
+            "big" => Ok(Width::Big),
+            _ => Err(format!("unknown width {:?}", s)),
+        }
+    }
+}
+
+#[test]
+fn test_sum_beyond_i64() {
+    let input = "+1\n+1, -1\n+2";
+    match sum_adjustments::<i64, _>(i64::MAX - 2, input.as_bytes()) {
+        Err(AdjustmentError::Overflow { bits: 64 }) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(
+        sum_adjustments::<i128, _>(i64::MAX - 2, input.as_bytes()).unwrap(),
+        i64::MAX as i128 + 1
+    );
+    assert_eq!(
+        Width::Big.sum(i64::MAX, input.as_bytes()).unwrap(),
+        "9223372036854775810"
+    );
+}
+
+#[test]
+fn test_adjusted_at_the_edges() {
+    // a big integer carries on past the edge of an i64 where the others stop.
+    let mut value = BigInt::start(i64::MIN);
+    for _ in 0..4 {
+        value = value.adjusted(i32::MIN).unwrap();
+    }
+    assert_eq!(value.to_string(), "-9223372045444710400");
+    assert_eq!(i128::MAX.adjusted(1), None);
+}
+
+#[test]
+fn test_width_from_str() {
+    assert_eq!("128".parse::<Width>(), Ok(Width::Bits128));
+    assert_eq!("big".parse::<Width>(), Ok(Width::Big));
+    assert!("32".parse::<Width>().is_err());
+}
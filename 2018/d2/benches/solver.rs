@@ -0,0 +1,8 @@
+extern crate common;
+extern crate d2;
+use common::bench::bench_solution;
+use d2::InventoryManagement;
+
+fn main() {
+    bench_solution::<InventoryManagement>("d2");
+}
@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Produce a simple checksum from the input.
 ///
@@ -16,78 +17,135 @@ use std::collections::HashMap;
 ///
 /// The resulting total is returned as the checksum.
 pub fn simple_checksum(input: &str) -> i64 {
-    let mut two_count = 0;
-    let mut three_count = 0;
+    audit(input, &[2, 3]).product()
+}
+
+/// Audit the box IDs in the input, a line each, counting how many have any letter exactly as many
+/// times as each of the multiplicities given. `simple_checksum` is an audit for two and three.
+pub fn audit<'a>(input: &'a str, multiplicities: &[usize]) -> Audit<'a> {
+    let wanted: BTreeSet<usize> = multiplicities.iter().cloned().collect();
+    let mut counts: BTreeMap<usize, usize> = wanted.iter().map(|m| (*m, 0)).collect();
+    let mut boxes = vec![];
 
     for line in input.lines() {
-        let results = check_string(line);
-        if results.two_letter {
-            two_count += 1;
-        }
-        if results.three_letter {
-            three_count += 1;
+        let satisfied = check_string(line, &wanted);
+        for multiplicity in &satisfied {
+            *counts.entry(*multiplicity).or_insert(0) += 1;
         }
+        boxes.push(BoxAudit {
+            id: line,
+            satisfied,
+        });
     }
 
-    return two_count * three_count;
+    Audit { counts, boxes }
 }
 
-/// Results of which counts the string matches.
-struct CheckResults {
-    two_letter: bool,
-    three_letter: bool,
+#[derive(Debug, Clone, PartialEq)]
+/// The outcome of auditing a list of box IDs for a set of letter multiplicities.
+pub struct Audit<'a> {
+    /// The number of boxes with a letter appearing exactly this many times, for every multiplicity
+    /// audited.
+    pub counts: BTreeMap<usize, usize>,
+    /// Every box in the order they were listed.
+    pub boxes: Vec<BoxAudit<'a>>,
 }
 
-/// Review the input string and look to see whether it should be included in the counts used for
-/// calculating a checksum.
-fn check_string(input: &str) -> CheckResults {
-    let mut letter_counts: HashMap<char, i64> = HashMap::new();
+impl<'a> Audit<'a> {
+    /// The counts multiplied together, which saturates rather than overflowing. Auditing no
+    /// multiplicities at all gives 1.
+    pub fn product(&self) -> i64 {
+        self.counts
+            .values()
+            .fold(1i64, |product, count| product.saturating_mul(*count as i64))
+    }
+}
 
-    // count each char repetition.
-    for c in input.chars() {
+#[derive(Debug, Clone, PartialEq)]
+/// Which of the audited multiplicities a single box ID had.
+pub struct BoxAudit<'a> {
+    pub id: &'a str,
+    /// The multiplicities with a letter appearing exactly that many times in the ID.
+    pub satisfied: BTreeSet<usize>,
+}
+
+/// Review the input string and find which of the wanted multiplicities it has a letter repeated
+/// exactly that many times for, to include it in the counts used for calculating a checksum.
+fn check_string(input: &str, wanted: &BTreeSet<usize>) -> BTreeSet<usize> {
+    let mut letter_counts: HashMap<&str, usize> = HashMap::new();
+
+    // count each letter repetition, a letter being what a reader would see as one character.
+    for c in input.graphemes(true) {
         let letter = letter_counts.entry(c).or_insert(0);
         *letter += 1;
     }
 
-    let mut found_two = false;
-    let mut found_three = false;
-
-    // review whether there are matches for either of the count criteria.
-    for (_letter, count) in letter_counts {
-        if count == 2 {
-            found_two = true;
-        }
-        if count == 3 {
-            found_three = true;
-        }
-    }
+    // review whether there are matches for any of the count criteria.
+    letter_counts
+        .values()
+        .filter(|count| wanted.contains(count))
+        .cloned()
+        .collect()
+}
 
-    return CheckResults {
-        two_letter: found_two,
-        three_letter: found_three,
-    };
+#[cfg(test)]
+fn two_and_three() -> BTreeSet<usize> {
+    [2, 3].iter().cloned().collect()
 }
 
 #[test]
 fn test_should_match_two_count() {
     let inp: &str = "aabcd";
-    let results = check_string(inp);
-    assert_eq!(results.two_letter, true);
-    assert_eq!(results.three_letter, false);
+    let results = check_string(inp, &two_and_three());
+    assert!(results.contains(&2));
+    assert!(!results.contains(&3));
 }
 
 #[test]
 fn test_should_match_three_count() {
     let inp: &str = "abafad";
-    let results = check_string(inp);
-    assert_eq!(results.two_letter, false);
-    assert_eq!(results.three_letter, true);
+    let results = check_string(inp, &two_and_three());
+    assert!(!results.contains(&2));
+    assert!(results.contains(&3));
 }
 
 #[test]
 fn test_should_match_two_and_three_count() {
     let inp: &str = "ababad";
-    let results = check_string(inp);
-    assert_eq!(results.two_letter, true);
-    assert_eq!(results.three_letter, true);
+    let results = check_string(inp, &two_and_three());
+    assert!(results.contains(&2));
+    assert!(results.contains(&3));
+}
+
+#[test]
+fn test_simple_checksum_example() {
+    let inp = "abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab";
+    assert_eq!(simple_checksum(inp), 12);
+}
+
+#[test]
+fn test_audit_multiplicities() {
+    let results = audit("aabbbcccc\nabcd\naaaa\nabab", &[4, 2, 3, 2]);
+    let counts: Vec<(usize, usize)> = results.counts.into_iter().collect();
+    assert_eq!(counts, vec![(2, 2), (3, 1), (4, 2)]);
+    assert_eq!(results.boxes[0].id, "aabbbcccc");
+    assert_eq!(
+        results.boxes[0].satisfied,
+        [2, 3, 4].iter().cloned().collect()
+    );
+    assert!(results.boxes[1].satisfied.is_empty());
+    assert_eq!(
+        audit("aabbbcccc\nabcd\naaaa\nabab", &[2, 3, 4]).product(),
+        4
+    );
+    assert_eq!(audit("aabbbcccc", &[]).product(), 1);
+}
+
+#[test]
+fn test_checksum_non_ascii() {
+    // the accented "é" is an "e" and a combining accent, it isn't another "e".
+    let results = check_string("eee\u{301}ñ", &two_and_three());
+    assert!(results.contains(&2));
+    assert!(!results.contains(&3));
+    assert_eq!(simple_checksum("ünïcödé\nüü\nööö"), 1);
 }
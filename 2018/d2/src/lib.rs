@@ -0,0 +1,80 @@
+extern crate common;
+extern crate unicode_segmentation;
+
+pub mod checksum;
+pub mod validate;
+pub mod warehouse;
+
+use common::generate::{Generate, Rng};
+use common::input::Input;
+use common::parse::ParseError;
+use common::solution::{Answer, Solution};
+
+/// Day 2: Inventory Management System.
+pub struct InventoryManagement;
+
+impl Solution for InventoryManagement {
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &Input) -> Result<&str, ParseError> {
+        Ok(input.as_str())
+    }
+
+    /// Checksum of the box IDs in the warehouse.
+    fn part1(box_ids: &&str) -> Answer {
+        checksum::simple_checksum(box_ids).into()
+    }
+
+    /// Letters common to the two box IDs that differ by exactly one character.
+    fn part2(box_ids: &&str) -> Answer {
+        warehouse::Warehouse::new(box_ids)
+            .look_for_best_matches()
+            .into()
+    }
+}
+
+/// Length of each box ID.
+const ID_LENGTH: usize = 26;
+
+impl Generate for InventoryManagement {
+    const REAL_SIZE: usize = 250;
+
+    /// Random box IDs of lowercase letters. One of them is copied with a single letter changed so
+    /// there is always a pair of boxes to find.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let letters: Vec<char> = ('a'..='z').collect();
+        let mut box_ids: Vec<Vec<char>> = (0..size)
+            .map(|_| (0..ID_LENGTH).map(|_| *rng.pick(&letters)).collect())
+            .collect();
+
+        if size > 1 {
+            let original = rng.below(size as u64) as usize;
+            let copy = (original + 1 + rng.below(size as u64 - 1) as usize) % size;
+            let col = rng.below(ID_LENGTH as u64) as usize;
+            box_ids[copy] = box_ids[original].clone();
+            while box_ids[copy][col] == box_ids[original][col] {
+                box_ids[copy][col] = *rng.pick(&letters);
+            }
+        }
+
+        let mut input = String::new();
+        for box_id in box_ids {
+            input.extend(box_id);
+            input.push('\n');
+        }
+        input
+    }
+}
+
+#[test]
+fn test_generated_input_has_a_matching_pair() {
+    use common::generate::{generate, Size};
+
+    let input = Input::new(&generate::<InventoryManagement>(Size::Records(40), 2));
+    let box_ids = InventoryManagement::parse(&input).unwrap();
+    assert_eq!(box_ids.lines().count(), 40);
+    match InventoryManagement::part2(&box_ids) {
+        Answer::Text(common) => assert_eq!(common.len(), ID_LENGTH - 1),
+        answer => panic!("unexpected answer {:?}", answer),
+    }
+}
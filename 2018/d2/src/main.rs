@@ -1,38 +1,119 @@
-mod checksum;
-mod warehouse;
+extern crate clap;
+extern crate common;
+extern crate d2;
 
-use std::error::Error;
-use std::fs::File;
-use std::io::prelude::*;
-use std::path::Path;
+use clap::{value_t, App, Arg};
+use common::input::{Input, DEFAULT_FILE_NAME, STDIN_ARG};
+use common::solution::print_answers;
+use d2::validate::{self, Allowed, Invalid, Rules};
+use d2::warehouse::Warehouse;
+use d2::InventoryManagement;
+use std::process;
 
 fn main() {
-    let file_name = "input";
-    let path = Path::new(file_name);
-
-    // Open the path in read-only mode, returns `io::Result<File>`
-    let mut file = match File::open(&path) {
-        // The `description` method of `io::Error` returns a string that
-        // describes the error
-        Err(why) => panic!("couldn't open {}: {}", file_name, why.description()),
-        Ok(file) => file,
+    let matches = App::new("d2")
+        .version("1.0")
+        .author("Jonathan Pulfer")
+        .about("Second puzzle solver for Advent Of Code 2018")
+        .arg(
+            Arg::with_name("input")
+                .value_name("PATH")
+                .help("File holding the box IDs, or - for stdin. Defaults to the input file")
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("max_diff")
+                .short("d")
+                .long("max-diff")
+                .value_name("COLUMNS")
+                .help("Instead of the answers, list every pair of box IDs differing in at most this many columns")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("validate")
+                .long("validate")
+                .help("Instead of the answers, report every line that isn't a valid box ID")
+                .conflicts_with("max_diff"),
+        )
+        .arg(
+            Arg::with_name("invalid")
+                .long("invalid")
+                .value_name("HANDLING")
+                .help("Use invalid box IDs as they are, skip them or refuse to go on when there are any")
+                .possible_values(Invalid::NAMES)
+                .default_value("keep")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("allow")
+                .long("allow")
+                .value_name("CHARACTERS")
+                .help("The characters a valid box ID is made of, a to z or letters from any alphabet")
+                .possible_values(Allowed::NAMES)
+                .default_value("lowercase")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("length")
+                .long("length")
+                .value_name("LENGTH")
+                .help("The length of a valid box ID. Defaults to the length most of them have")
+                .takes_value(true),
+        )
+        .get_matches();
+
+    let input = match matches.value_of("input").unwrap_or(DEFAULT_FILE_NAME) {
+        STDIN_ARG => Input::from_stdin(),
+        path => Input::from_path(path),
+    }
+    .unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+
+    let rules = Rules {
+        length: if matches.is_present("length") {
+            Some(value_t!(matches, "length", usize).unwrap_or_else(|e| e.exit()))
+        } else {
+            None
+        },
+        allowed: value_t!(matches, "allow", Allowed).unwrap_or_else(|e| e.exit()),
     };
 
-    let contents: &mut String = &mut String::new();
+    if matches.is_present("validate") {
+        let validation = validate::validate(input.as_str(), &rules);
+        print!("{}", validation);
+        if !validation.is_valid() {
+            process::exit(1);
+        }
+        return;
+    }
 
-    // Read the file contents into a string, returns `io::Result<usize>`
-    match file.read_to_string(contents) {
-        Err(why) => panic!("couldn't read {}: {}", file_name, why.description()),
-        Ok(_) => println!("{} read", file_name),
+    let invalid = value_t!(matches, "invalid", Invalid).unwrap_or_else(|e| e.exit());
+    let skip = validate::prepare(input.as_str(), &rules, invalid).unwrap_or_else(|validation| {
+        eprint!("{}", validation);
+        process::exit(1);
+    });
+
+    if matches.is_present("max_diff") {
+        let max_diff = value_t!(matches, "max_diff", usize).unwrap_or_else(|e| e.exit());
+        // the skipped addresses are left out rather than the input rebuilt, so the lines listed
+        // are still those of the input.
+        let warehouse = Warehouse::new(input.as_str()).without_lines(&skip);
+        for near_match in warehouse.near_matches(max_diff) {
+            println!("{}", near_match);
+        }
+        return;
     }
 
-    // generate a simple checksum from the file contents.
-    println!(
-        "simple_checksum: {}",
-        checksum::simple_checksum(contents.as_str())
-    );
+    let input = if skip.is_empty() {
+        input
+    } else {
+        Input::new(&validate::without_lines(input.as_str(), &skip))
+    };
 
-    let warehouse = warehouse::Warehouse::new(contents.as_str());
-    let match_chars = warehouse.look_for_best_matches();
-    println!("match_chars: {}", match_chars);
+    if let Err(err) = print_answers::<InventoryManagement>(&input) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
 }
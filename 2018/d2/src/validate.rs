@@ -0,0 +1,332 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The characters a box ID may be made of.
+pub enum Allowed {
+    /// Only `a` to `z`, as in the puzzle input.
+    AsciiLowercase,
+    /// Letters from any alphabet, in either case and with any accents.
+    Letters,
+}
+
+impl Allowed {
+    pub const NAMES: &'static [&'static str] = &["lowercase", "letters"];
+
+    fn allows(self, grapheme: &str) -> bool {
+        match self {
+            Allowed::AsciiLowercase => {
+                grapheme.len() == 1 && grapheme.as_bytes()[0].is_ascii_lowercase()
+            }
+            // the accents on a letter are marks, only the letter they sit on needs checking.
+            Allowed::Letters => grapheme.chars().next().is_some_and(char::is_alphabetic),
+        }
+    }
+}
+
+impl FromStr for Allowed {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Allowed, String> {
+        match s {
+            "lowercase" => Ok(Allowed::AsciiLowercase),
+            "letters" => Ok(Allowed::Letters),
+            _ => Err(format!("unknown characters {:?}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// What a box ID has to look like to be valid.
+pub struct Rules {
+    /// The number of characters in every ID. When left out it is the length most of the IDs have.
+    pub length: Option<usize>,
+    pub allowed: Allowed,
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules {
+            length: None,
+            allowed: Allowed::AsciiLowercase,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Something wrong with a box ID.
+pub enum Problem {
+    /// The line is empty, or only whitespace.
+    Empty,
+    /// The ID isn't the length every ID should be.
+    Length { expected: usize, found: usize },
+    /// The same ID is on an earlier line, counted from 1.
+    Duplicate { first_line: usize },
+    /// The ID has characters that aren't allowed, each listed once in the order found.
+    Disallowed(Vec<String>),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Empty => write!(f, "empty"),
+            Problem::Length { expected, found } => {
+                write!(f, "{} characters, expected {}", found, expected)
+            }
+            Problem::Duplicate { first_line } => write!(f, "duplicate of line {}", first_line),
+            Problem::Disallowed(graphemes) => {
+                let quoted: Vec<String> = graphemes.iter().map(|g| format!("{:?}", g)).collect();
+                write!(f, "disallowed characters {}", quoted.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A problem found on a line of the input, the line counted from 1.
+pub struct Issue {
+    pub line: usize,
+    pub problem: Problem,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Everything wrong with a list of box IDs.
+pub struct Validation {
+    /// The length the IDs were checked against, None when there were no IDs to go on.
+    pub expected_length: Option<usize>,
+    /// Every problem found, in line order. A line can have more than one.
+    pub issues: Vec<Issue>,
+}
+
+impl Validation {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// The lines with a problem, counted from 1.
+    pub fn invalid_lines(&self) -> HashSet<usize> {
+        self.issues.iter().map(|issue| issue.line).collect()
+    }
+
+    /// The input with every line that has a problem left out.
+    pub fn without_invalid(&self, input: &str) -> String {
+        without_lines(input, &self.invalid_lines())
+    }
+}
+
+/// The input with the given lines, counted from 1, left out.
+pub fn without_lines(input: &str, skip: &HashSet<usize>) -> String {
+    let mut kept = String::new();
+    for (index, line) in input.lines().enumerate() {
+        if !skip.contains(&(index + 1)) {
+            kept.push_str(line);
+            kept.push('\n');
+        }
+    }
+    kept
+}
+
+impl fmt::Display for Validation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_valid() {
+            return writeln!(f, "every box ID is valid");
+        }
+        for issue in &self.issues {
+            writeln!(f, "line {}: {}", issue.line, issue.problem)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check every line of the input is a box ID following the rules. Lengths are counted in grapheme
+/// clusters, as the checksum and warehouse count them.
+pub fn validate(input: &str, rules: &Rules) -> Validation {
+    let expected_length = rules.length.or_else(|| most_common_length(input));
+    let mut issues = vec![];
+    let mut first_seen: HashMap<&str, usize> = HashMap::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            issues.push(Issue {
+                line: line_number,
+                problem: Problem::Empty,
+            });
+            continue;
+        }
+
+        let graphemes: Vec<&str> = line.graphemes(true).collect();
+        if let Some(expected) = expected_length {
+            if graphemes.len() != expected {
+                issues.push(Issue {
+                    line: line_number,
+                    problem: Problem::Length {
+                        expected,
+                        found: graphemes.len(),
+                    },
+                });
+            }
+        }
+
+        let mut disallowed: Vec<String> = vec![];
+        for grapheme in graphemes {
+            if !rules.allowed.allows(grapheme) && !disallowed.iter().any(|d| d == grapheme) {
+                disallowed.push(grapheme.to_string());
+            }
+        }
+        if !disallowed.is_empty() {
+            issues.push(Issue {
+                line: line_number,
+                problem: Problem::Disallowed(disallowed),
+            });
+        }
+
+        match first_seen.get(line) {
+            Some(first_line) => issues.push(Issue {
+                line: line_number,
+                problem: Problem::Duplicate {
+                    first_line: *first_line,
+                },
+            }),
+            None => {
+                first_seen.insert(line, line_number);
+            }
+        }
+    }
+
+    Validation {
+        expected_length,
+        issues,
+    }
+}
+
+/// The length most of the non-empty lines have, the shortest of those when there is a tie.
+fn most_common_length(input: &str) -> Option<usize> {
+    let mut lengths: HashMap<usize, usize> = HashMap::new();
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        *lengths.entry(line.graphemes(true).count()).or_insert(0) += 1;
+    }
+    lengths
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(length, _)| length)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// What to do with box IDs that aren't valid.
+pub enum Invalid {
+    /// Use them as they are.
+    Keep,
+    /// Leave them out.
+    Skip,
+    /// Refuse to go on if there are any.
+    Reject,
+}
+
+impl Invalid {
+    pub const NAMES: &'static [&'static str] = &["keep", "skip", "reject"];
+}
+
+impl FromStr for Invalid {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Invalid, String> {
+        match s {
+            "keep" => Ok(Invalid::Keep),
+            "skip" => Ok(Invalid::Skip),
+            "reject" => Ok(Invalid::Reject),
+            _ => Err(format!("unknown handling {:?}", s)),
+        }
+    }
+}
+
+/// The lines of the input, counted from 1, to leave out of the box IDs having dealt with any
+/// invalid ones as asked. Rejecting them hands back the validation when there were any.
+pub fn prepare(input: &str, rules: &Rules, invalid: Invalid) -> Result<HashSet<usize>, Validation> {
+    if invalid == Invalid::Keep {
+        return Ok(HashSet::new());
+    }
+    let validation = validate(input, rules);
+    match invalid {
+        Invalid::Reject if !validation.is_valid() => Err(validation),
+        Invalid::Skip => Ok(validation.invalid_lines()),
+        _ => Ok(HashSet::new()),
+    }
+}
+
+#[cfg(test)]
+const MIXED: &str = "abcde\n\nabcde\nabcd\nabCde\nfghij\n  \nab1d2";
+
+#[test]
+fn test_validate() {
+    let validation = validate(MIXED, &Rules::default());
+    assert_eq!(validation.expected_length, Some(5));
+    assert_eq!(
+        validation.to_string(),
+        "line 2: empty\n\
+         line 3: duplicate of line 1\n\
+         line 4: 4 characters, expected 5\n\
+         line 5: disallowed characters \"C\"\n\
+         line 7: empty\n\
+         line 8: disallowed characters \"1\", \"2\"\n"
+    );
+    assert_eq!(validation.without_invalid(MIXED), "abcde\nfghij\n");
+}
+
+#[test]
+fn test_validate_letters() {
+    let rules = Rules {
+        length: Some(4),
+        allowed: Allowed::Letters,
+    };
+    let validation = validate("ñandú\nCafe\u{301}\nsöße\nwr0ng", &rules);
+    let problems: Vec<(usize, &Problem)> = validation
+        .issues
+        .iter()
+        .map(|issue| (issue.line, &issue.problem))
+        .collect();
+    assert_eq!(
+        problems,
+        vec![
+            (
+                1,
+                &Problem::Length {
+                    expected: 4,
+                    found: 5
+                }
+            ),
+            (
+                4,
+                &Problem::Length {
+                    expected: 4,
+                    found: 5
+                }
+            ),
+            (4, &Problem::Disallowed(vec!["0".to_string()])),
+        ]
+    );
+    assert!(validate("abc\nabd", &Rules::default()).is_valid());
+}
+
+#[test]
+fn test_prepare() {
+    let rules = Rules::default();
+    assert!(prepare(MIXED, &rules, Invalid::Keep).unwrap().is_empty());
+    let skip = prepare(MIXED, &rules, Invalid::Skip).unwrap();
+    let mut skipped: Vec<usize> = skip.iter().cloned().collect();
+    skipped.sort();
+    assert_eq!(skipped, vec![2, 3, 4, 5, 7, 8]);
+    assert_eq!(without_lines(MIXED, &skip), "abcde\nfghij\n");
+    assert_eq!(
+        prepare(MIXED, &rules, Invalid::Reject)
+            .unwrap_err()
+            .issues
+            .len(),
+        6
+    );
+    assert!(prepare("abc\nabd", &rules, Invalid::Reject)
+        .unwrap()
+        .is_empty());
+}
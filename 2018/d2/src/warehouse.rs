@@ -1,7 +1,14 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
+use std::mem;
+use unicode_segmentation::UnicodeSegmentation;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 /// A box address from a mythical warehouse which may or may not contain items of importance to
 /// a certain festive altruist.
+///
+/// Addresses are compared a grapheme cluster at a time, what a reader would see as a single
+/// character, so a letter with an accent made of several code points is still one column.
 pub struct Address<'a> {
     raw: &'a str,
 }
@@ -16,16 +23,21 @@ impl<'a> Address<'a> {
     /// way, the tuple returned will indicate the comparison is true and the col the differing
     /// character is in.
     pub fn compare(&self, other: &Address) -> (bool, usize) {
+        let ours = self.graphemes();
+        let theirs = other.graphemes();
+        // masking the same column of each only lines them up when they are the same length.
+        if ours.len() != theirs.len() {
+            return (false, 0);
+        }
         let mut matched = false;
         let mut col: usize = 0;
 
-        for p in 0..other.raw.len() {
-            let our_parts = self.raw.split_at(p);
-            let our_mask: String = [our_parts.0, "?", &our_parts.1[1..our_parts.1.len()]].join("");
+        for p in 0..theirs.len() {
+            let mut our_mask = ours.clone();
+            our_mask[p] = "?";
 
-            let other_parts = other.raw.split_at(p);
-            let other_mask: String =
-                [other_parts.0, "?", &other_parts.1[1..other_parts.1.len()]].join("");
+            let mut other_mask = theirs.clone();
+            other_mask[p] = "?";
 
             if our_mask.eq(&other_mask) {
                 matched = true;
@@ -34,15 +46,80 @@ impl<'a> Address<'a> {
             }
         }
 
-        return (matched, col);
+        (matched, col)
+    }
+
+    /// The columns this address differs from the other in, or None when there are more than
+    /// `limit` of them. Addresses of different lengths have no columns in common to compare.
+    pub fn differing_cols(&self, other: &Address, limit: usize) -> Option<Vec<usize>> {
+        let ours = self.graphemes();
+        let theirs = other.graphemes();
+        if ours.len() != theirs.len() {
+            return None;
+        }
+        let mut cols = vec![];
+        for (col, (ours, theirs)) in ours.iter().zip(theirs.iter()).enumerate() {
+            if ours != theirs {
+                if cols.len() == limit {
+                    return None;
+                }
+                cols.push(col);
+            }
+        }
+        Some(cols)
+    }
+
+    /// The fewest single character insertions, deletions, substitutions and swaps of adjacent
+    /// characters needed to turn this address into the other. No character is edited more than
+    /// once, which is the restricted form of the Damerau-Levenshtein distance.
+    pub fn edit_distance(&self, other: &Address) -> usize {
+        let ours = self.graphemes();
+        let theirs = other.graphemes();
+
+        // each row holds the distances from a prefix of ours to every prefix of theirs, only the
+        // last two rows are needed to work out the next.
+        let mut before: Vec<usize> = vec![0; theirs.len() + 1];
+        let mut previous: Vec<usize> = (0..=theirs.len()).collect();
+        let mut current: Vec<usize> = vec![0; theirs.len() + 1];
+        for i in 1..=ours.len() {
+            current[0] = i;
+            for j in 1..=theirs.len() {
+                let substitution = if ours[i - 1] == theirs[j - 1] { 0 } else { 1 };
+                current[j] = (previous[j] + 1)
+                    .min(current[j - 1] + 1)
+                    .min(previous[j - 1] + substitution);
+                if i > 1 && j > 1 && ours[i - 1] == theirs[j - 2] && ours[i - 2] == theirs[j - 1] {
+                    current[j] = current[j].min(before[j - 2] + 1);
+                }
+            }
+            mem::swap(&mut before, &mut previous);
+            mem::swap(&mut previous, &mut current);
+        }
+        previous[theirs.len()]
+    }
+
+    /// How far apart this address is from the other when measured in the way given, or None when
+    /// they can't be compared that way.
+    pub fn distance(&self, other: &Address, similarity: Similarity) -> Option<usize> {
+        match similarity {
+            Similarity::Columns => self
+                .differing_cols(other, usize::MAX)
+                .map(|cols| cols.len()),
+            Similarity::Edits => Some(self.edit_distance(other)),
+        }
     }
 
     /// Once a comparison indicates which column a match differs on, this will return the characters
     /// except the indicated one which will be dropped.
     pub fn drop_col(&self, col: usize) -> String {
-        let our_parts = self.raw.split_at(col);
-        let our_result: String = [our_parts.0, &our_parts.1[1..our_parts.1.len()]].join("");
-        return our_result;
+        let mut ours = self.graphemes();
+        ours.remove(col);
+        ours.concat()
+    }
+
+    /// The characters of the address as grapheme clusters, each one a column.
+    pub fn graphemes(&self) -> Vec<&'a str> {
+        self.raw.graphemes(true).collect()
     }
 }
 
@@ -52,6 +129,38 @@ impl<'a> fmt::Display for Address<'a> {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Ways of measuring how similar two addresses are.
+pub enum Similarity {
+    /// The number of columns they differ in, only addresses of the same length can be compared.
+    Columns,
+    /// The number of characters inserted, deleted, substituted or swapped to turn one into the
+    /// other.
+    Edits,
+}
+
+#[test]
+fn test_edit_distance() {
+    let distance = |a, b| Address::new(a).edit_distance(&Address::new(b));
+    assert_eq!(distance("abcdefg", "abcrefg"), 1);
+    assert_eq!(distance("abcdefg", "abcefg"), 1);
+    assert_eq!(distance("abcdefg", "abcdxefg"), 1);
+    assert_eq!(distance("abcdefg", "abdcefg"), 1);
+    assert_eq!(distance("kitten", "sitting"), 3);
+    assert_eq!(distance("", "abc"), 3);
+    assert_eq!(distance("ca", "abc"), 3);
+}
+
+#[test]
+fn test_distance_by_similarity() {
+    let addr1 = Address::new("abcdefg");
+    let addr2 = Address::new("abdcefgh");
+    assert_eq!(addr1.distance(&addr2, Similarity::Columns), None);
+    assert_eq!(addr1.distance(&addr2, Similarity::Edits), Some(2));
+    // comparing with a longer address used to run off the end of this one.
+    assert_eq!(addr1.compare(&addr2), (false, 0));
+}
+
 #[test]
 fn test_compare() {
     let addr1 = Address::new("abcdefg");
@@ -64,46 +173,301 @@ fn test_compare() {
 pub struct Warehouse<'a> {
     /// List of addresses within this warehouse. These are read from the input lines.
     addresses: Vec<Address<'a>>,
+    /// The line of the input each address was read from, counted from 1.
+    lines: Vec<usize>,
 }
 
 impl<'a> Warehouse<'a> {
     /// Create a new warehouse catering for addresses with the provided number of cols.
     pub fn new(input: &'a str) -> Warehouse<'a> {
         let mut addresses = vec![];
+        let mut lines = vec![];
 
-        for line in input.lines() {
-            let mut addr: Address = Address::new(line);
+        for (index, line) in input.lines().enumerate() {
+            let addr: Address = Address::new(line);
 
             addresses.push(addr);
+            lines.push(index + 1);
         }
 
-        Warehouse { addresses }
+        Warehouse { addresses, lines }
+    }
+
+    /// The warehouse without the addresses read from the given lines of the input, counted from 1.
+    /// The addresses left keep the lines they were read from.
+    pub fn without_lines(self, skip: &HashSet<usize>) -> Warehouse<'a> {
+        let (addresses, lines) = self
+            .addresses
+            .into_iter()
+            .zip(self.lines)
+            .filter(|(_, line)| !skip.contains(line))
+            .unzip();
+        Warehouse { addresses, lines }
+    }
+
+    /// Find the two addresses that differ by exactly one character and return the characters they
+    /// have in common, or an empty string when there are none.
+    ///
+    /// Two addresses differ only in column `c` when they are the same with that column removed, so
+    /// each address is indexed by what is left of it with each column removed in turn. The first
+    /// address whose remains are already in the index for a column, with a different character in
+    /// that column, completes the pair. The remains are slices of the addresses so nothing is
+    /// copied.
+    pub fn look_for_best_matches(&self) -> String {
+        let mut index: Vec<HashMap<(&str, &str), &str>> = vec![];
+        for addr in &self.addresses {
+            let raw = addr.raw;
+            for (col, (p, grapheme)) in raw.grapheme_indices(true).enumerate() {
+                let remains = (&raw[..p], &raw[p + grapheme.len()..]);
+                if index.len() <= col {
+                    index.push(HashMap::new());
+                }
+                // the whole grapheme is compared, an "e" isn't the same as an "e" with an accent.
+                match index[col].get(&remains) {
+                    Some(other) if *other != grapheme => {
+                        return [remains.0, remains.1].concat();
+                    }
+                    Some(_) => {}
+                    None => {
+                        index[col].insert(remains, grapheme);
+                    }
+                }
+            }
+        }
+        "".to_string()
     }
 
     /// Work through the list of addresses in the warehouse and compare each one to the rest of the
     /// addresses in the list. Return the matching characters that meet the matching criteria of
     /// puzzle.
-    pub fn look_for_best_matches(&self) -> String {
-        // track the position of the left address to ignore as a right address.
-        let mut left_pos = 0;
-
-        for a_left in self.addresses.iter() {
-            // track the right position for the comparison to the left (outer) position.
-            let mut right_pos = 0;
-            for a_right in self.addresses.iter() {
+    ///
+    /// This is much slower than `look_for_best_matches` and is kept to check it against.
+    pub fn look_for_best_matches_pairwise(&self) -> String {
+        for (left_pos, a_left) in self.addresses.iter().enumerate() {
+            for (right_pos, a_right) in self.addresses.iter().enumerate() {
                 // ignore the address from the same position as this will definitely be a match but
                 // not the one we are looking for!
                 if left_pos == right_pos {
                     continue;
                 }
-                let (matched, col) = a_left.compare(&a_right);
+                let (matched, col) = a_left.compare(a_right);
                 if matched {
                     return a_right.drop_col(col);
                 }
-                right_pos += 1;
             }
-            left_pos += 1;
         }
-        return "".to_string();
+        "".to_string()
+    }
+
+    /// Every pair of addresses that differ in at least one and at most `max_diff` columns, in the
+    /// order they are listed. Identical addresses aren't near matches.
+    pub fn near_matches(&self, max_diff: usize) -> Vec<NearMatch<'a>> {
+        let mut matches = vec![];
+        for (left, a_left) in self.addresses.iter().enumerate() {
+            for (right, a_right) in self.addresses.iter().enumerate().skip(left + 1) {
+                let cols = match a_left.differing_cols(a_right, max_diff) {
+                    Some(ref cols) if cols.is_empty() => continue,
+                    Some(cols) => cols,
+                    None => continue,
+                };
+                let common = a_left
+                    .graphemes()
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(col, _)| cols.binary_search(col).is_err())
+                    .map(|(_, grapheme)| grapheme)
+                    .collect();
+                matches.push(NearMatch {
+                    left,
+                    right,
+                    lines: (self.lines[left], self.lines[right]),
+                    addresses: (*a_left, *a_right),
+                    cols,
+                    common,
+                });
+            }
+        }
+        matches
+    }
+
+    /// Group the addresses so that each is within `max_distance` of at least one other in its
+    /// group, measured in the way given. Each group holds the positions of its addresses in the
+    /// warehouse, counted from 0 and in order, and the groups are in order of their first address.
+    /// Addresses that aren't similar to any other are left out.
+    pub fn clusters(&self, max_distance: usize, similarity: Similarity) -> Vec<Vec<usize>> {
+        // each address starts in a group of its own, joining groups as similar pairs are found.
+        let mut parents: Vec<usize> = (0..self.addresses.len()).collect();
+
+        for (left, a_left) in self.addresses.iter().enumerate() {
+            for (right, a_right) in self.addresses.iter().enumerate().skip(left + 1) {
+                let similar = a_left
+                    .distance(a_right, similarity)
+                    .is_some_and(|distance| distance <= max_distance);
+                if similar {
+                    let (l, r) = (root(&mut parents, left), root(&mut parents, right));
+                    parents[l.max(r)] = l.min(r);
+                }
+            }
+        }
+
+        let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for at in 0..self.addresses.len() {
+            let group = root(&mut parents, at);
+            groups.entry(group).or_default().push(at);
+        }
+        groups
+            .into_values()
+            .filter(|group| group.len() > 1)
+            .collect()
+    }
+}
+
+/// The first address in the group holding the address at `at`, shortening the path to it on the
+/// way.
+fn root(parents: &mut [usize], mut at: usize) -> usize {
+    while parents[at] != at {
+        parents[at] = parents[parents[at]];
+        at = parents[at];
+    }
+    at
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Two addresses that differ in only a few columns.
+pub struct NearMatch<'a> {
+    /// Position of the first address in the warehouse, counted from 0.
+    pub left: usize,
+    /// Position of the second address, always after the first.
+    pub right: usize,
+    /// The lines of the input the addresses were read from, counted from 1.
+    pub lines: (usize, usize),
+    pub addresses: (Address<'a>, Address<'a>),
+    /// The columns the addresses differ in, counted from 0.
+    pub cols: Vec<usize>,
+    /// The characters the addresses have in common, in order.
+    pub common: String,
+}
+
+impl<'a> fmt::Display for NearMatch<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cols: Vec<String> = self.cols.iter().map(|col| (col + 1).to_string()).collect();
+        write!(
+            f,
+            "lines {} and {} ({} and {}) differ at {} {}, in common: {}",
+            self.lines.0,
+            self.lines.1,
+            self.addresses.0,
+            self.addresses.1,
+            if cols.len() == 1 { "column" } else { "columns" },
+            cols.join(", "),
+            self.common
+        )
+    }
+}
+
+#[test]
+fn test_look_for_best_matches() {
+    let warehouse = Warehouse::new("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz");
+    assert_eq!(warehouse.look_for_best_matches(), "fgij");
+    // the same ID twice doesn't differ by a character at all.
+    assert_eq!(Warehouse::new("abc\nabc\nxyz").look_for_best_matches(), "");
+    assert_eq!(Warehouse::new("abc\nab\nabcd").look_for_best_matches(), "");
+}
+
+#[test]
+fn test_look_for_best_matches_agrees_with_pairwise() {
+    use common::generate::{generate, Size};
+    use InventoryManagement;
+
+    for seed in 0..20 {
+        let input = generate::<InventoryManagement>(Size::Records(60), seed);
+        let warehouse = Warehouse::new(&input);
+        assert_eq!(
+            warehouse.look_for_best_matches(),
+            warehouse.look_for_best_matches_pairwise(),
+            "seed {}",
+            seed
+        );
+    }
+}
+
+#[test]
+fn test_near_matches() {
+    let warehouse = Warehouse::new("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nabcde\nab");
+    let matches = warehouse.near_matches(1);
+    assert_eq!(matches.len(), 1);
+    assert_eq!((matches[0].left, matches[0].right), (1, 4));
+    assert_eq!(matches[0].cols, vec![2]);
+    assert_eq!(matches[0].common, "fgij");
+    assert_eq!(
+        matches[0].to_string(),
+        "lines 2 and 5 (fghij and fguij) differ at column 3, in common: fgij"
+    );
+
+    let matches = warehouse.near_matches(2);
+    let pairs: Vec<(usize, usize)> = matches.iter().map(|m| (m.left, m.right)).collect();
+    assert_eq!(pairs, vec![(0, 5), (1, 4), (5, 6)]);
+    assert_eq!(matches[0].cols, vec![1, 3]);
+    assert_eq!(matches[0].common, "ace");
+}
+
+#[test]
+fn test_near_matches_keep_their_lines() {
+    let skip: HashSet<usize> = [1, 3].iter().cloned().collect();
+    let warehouse = Warehouse::new(
+        "abcde
+fghij
+klmno
+pqrst
+fguij",
+    )
+    .without_lines(&skip);
+    let matches = warehouse.near_matches(1);
+    assert_eq!((matches[0].left, matches[0].right), (0, 2));
+    assert_eq!(matches[0].lines, (2, 5));
+    assert_eq!(
+        matches[0].to_string(),
+        "lines 2 and 5 (fghij and fguij) differ at column 3, in common: fgij"
+    );
+}
+
+#[test]
+fn test_clusters() {
+    let warehouse = Warehouse::new("abcde\nzzzzz\nabcdf\nbacde\nabdcf\nzzzz\nqwerty");
+    assert_eq!(warehouse.clusters(1, Similarity::Columns), vec![vec![0, 2]]);
+    assert_eq!(
+        warehouse.clusters(1, Similarity::Edits),
+        vec![vec![0, 2, 3, 4], vec![1, 5]]
+    );
+    assert!(warehouse.clusters(0, Similarity::Edits).is_empty());
+}
+
+#[test]
+fn test_non_ascii_addresses() {
+    // "é" is written as an "e" followed by a combining accent, two code points in one column.
+    let accented = "cafe\u{301}-ñandú";
+    let addr1 = Address::new(accented);
+    let addr2 = Address::new("cafe-ñandú");
+    let addr3 = Address::new("cafe\u{301}-ñandü");
+    assert_eq!(addr1.graphemes().len(), 10);
+    assert_eq!(addr1.compare(&addr2), (true, 3));
+    assert_eq!(addr1.drop_col(3), "caf-ñandú");
+    assert_eq!(addr1.differing_cols(&addr3, 5), Some(vec![9]));
+    assert_eq!(addr2.edit_distance(&Address::new("caféñandú")), 2);
+
+    let warehouse = Warehouse::new("📦🎁🎄🎅\nxyzw\n📦🎁🎄🤶");
+    assert_eq!(warehouse.look_for_best_matches(), "📦🎁🎄");
+    assert_eq!(warehouse.look_for_best_matches_pairwise(), "📦🎁🎄");
+    assert_eq!(warehouse.near_matches(1)[0].common, "📦🎁🎄");
+
+    let input = [accented, "cafe\u{301}-ñandü"].join("\n");
+    let warehouse = Warehouse::new(&input);
+    assert_eq!(warehouse.look_for_best_matches(), "cafe\u{301}-ñand");
+
+    // the accented line first, so the plain "e" is looked up against it.
+    for input in &["cafe\u{301}-x\ncafe-x", "cafe-x\ncafe\u{301}-x"] {
+        let warehouse = Warehouse::new(input);
+        assert_eq!(warehouse.look_for_best_matches(), "caf-x");
+        assert_eq!(warehouse.look_for_best_matches_pairwise(), "caf-x");
     }
 }
@@ -0,0 +1,6 @@
+use common::bench::bench_solution;
+use d3::NoMatterHowYouSliceIt;
+
+fn main() {
+    bench_solution::<NoMatterHowYouSliceIt>("d3");
+}
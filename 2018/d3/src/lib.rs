@@ -0,0 +1,65 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod workshop;
+
+use common::generate::{Generate, Rng};
+use common::input::Input;
+use common::parse::{parse_lines, ParseError};
+use common::solution::{Answer, Solution};
+
+/// Day 3: No Matter How You Slice It.
+pub struct NoMatterHowYouSliceIt;
+
+impl Solution for NoMatterHowYouSliceIt {
+    type Parsed<'a> = workshop::Fabric<'a>;
+
+    /// Lay every claim from the input out on a fresh piece of fabric.
+    fn parse(input: &Input) -> Result<workshop::Fabric<'_>, ParseError> {
+        let mut fabric = workshop::Fabric::new();
+        for claim in parse_lines(input.as_str(), workshop::Claim::from_input_line)? {
+            fabric.allocate(claim);
+        }
+        Ok(fabric)
+    }
+
+    /// Number of square inches of fabric within two or more claims.
+    fn part1(fabric: &workshop::Fabric<'_>) -> Answer {
+        fabric.count_over_allocated_squares().into()
+    }
+
+    /// The ID of the only claim that doesn't overlap any other.
+    fn part2(fabric: &workshop::Fabric<'_>) -> Answer {
+        fabric.find_claim_without_collisions().into()
+    }
+}
+
+impl Generate for NoMatterHowYouSliceIt {
+    const REAL_SIZE: usize = 1383;
+
+    /// Claims of 10 to 29 inches a side placed anywhere on the fabric, as in the real input.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+        for id in 1..=size {
+            let (wide, tall) = (rng.between(10, 29), rng.between(10, 29));
+            input.push_str(&format!(
+                "#{} @ {},{}: {}x{}\n",
+                id,
+                rng.between(0, workshop::FABRIC_SIZE as i64 - wide),
+                rng.between(0, workshop::FABRIC_SIZE as i64 - tall),
+                wide,
+                tall
+            ));
+        }
+        input
+    }
+}
+
+#[test]
+fn test_generated_input_parses() {
+    use common::generate::{generate, Size};
+
+    let input = Input::new(&generate::<NoMatterHowYouSliceIt>(Size::Records(200), 3));
+    let fabric = NoMatterHowYouSliceIt::parse(&input).unwrap();
+    assert!(fabric.count_over_allocated_squares() > 0);
+}
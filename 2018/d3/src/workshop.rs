@@ -1,89 +1,177 @@
+use common::parse::ParseError;
 use regex::Regex;
-use std::collections::hash_map::Entry;
-use std::collections::HashMap;
 
 lazy_static! {
     static ref CLAIMRE: Regex = Regex::new(r"^#(\d+)\s@\s(\d+),(\d+):\s(\d+)x(\d+)$").unwrap();
 }
 
+/// Length of each side of the fabric in the puzzle in inches. Claims can reach beyond it, the
+/// fabric is as large as the claims made on it.
+pub const FABRIC_SIZE: usize = 1000;
+
 #[derive(Debug)]
 /// A large, rectangular piece of fabric as discovered in a box lost in the mythical warehouse from
 /// puzzle 2. This magical fabric is being fought over by the elves who are more than a little
 /// fraught by a last minute requirement to make something (I can certainly sympathise with this
 /// scenario!).
 pub struct Fabric<'a> {
-    grid: Vec<Vec<Allocation<'a>>>,
+    claims: Vec<Claim<'a>>,
 }
 
 impl<'a> Fabric<'a> {
     pub fn new() -> Fabric<'a> {
-        let max_size = 1000;
-        let grid: Vec<Vec<Allocation>> =
-            vec![vec!(Allocation { claims: vec!() }, max_size); max_size];
-
-        return Fabric { grid };
+        Fabric { claims: vec![] }
     }
 
     /// Apply the claim to the fabric.
     pub fn allocate(&mut self, claim: Claim<'a>) {
-        for coord in claim.coordinates() {
-            self.grid[coord.from_top as usize][coord.from_left as usize]
-                .claims
-                .push(claim.clone());
-        }
+        self.claims.push(claim);
     }
 
     /// Work through all the grid squares and count those that are allocated more than once.
     pub fn count_over_allocated_squares(&self) -> i64 {
+        let coverage = Coverage::new(&self.claims);
         let mut over_allocations: i64 = 0;
-        for y in self.grid.iter() {
-            for x in y {
-                if x.claims.len() > 1 {
-                    over_allocations += 1;
+        for (row, height) in coverage.rows() {
+            for (col, width) in coverage.cols() {
+                if coverage.count(row, col) > 1 {
+                    over_allocations += width * height;
                 }
             }
         }
         over_allocations
     }
 
-    /// Search through the fabric allocations and record whether there are collisions for each claim
-    /// seen. If there is a claim that has no collisions the claim id will be returned.
+    /// Look for a claim that doesn't share any of its squares with another claim and return its id,
+    /// the first one listed if there is more than one.
     pub fn find_claim_without_collisions(&self) -> String {
-        let mut claim_collision_counts: HashMap<String, u32> = HashMap::new();
-        let mut claim_seen: HashMap<String, bool> = HashMap::new();
-
-        // Work through the allocated grid squares and record all claims seen in one map and in
-        // another map, record colliding claims.
-        for y in self.grid.iter() {
-            for x in y {
-                if x.claims.len() == 1 {
-                    let seen = claim_seen
-                        .entry(x.claims[0].id.to_string())
-                        .or_insert(false);
-                    *seen = true;
-                }
-                if x.claims.len() > 1 {
-                    for cl in x.claims.iter() {
-                        let claim_id = String::from(cl.id);
-                        let collided = claim_collision_counts.entry(claim_id).or_insert(0);
-                        *collided += 1;
-                    }
-                }
+        let coverage = Coverage::new(&self.claims);
+        for claim in &self.claims {
+            let (top, left, bottom, right) = claim.edges();
+            // a claim without any squares can't be said to be alone on the fabric.
+            if top == bottom || left == right {
+                continue;
+            }
+            let rows = coverage.row_index(top)..coverage.row_index(bottom);
+            let cols = coverage.col_index(left)..coverage.col_index(right);
+            let alone = rows
+                .flat_map(|row| cols.clone().map(move |col| (row, col)))
+                .all(|(row, col)| coverage.count(row, col) == 1);
+            if alone {
+                return claim.id.to_string();
             }
         }
 
-        // Work through the two maps to see whether each seen claim appears in the map recording
-        // those that collide with other claims.
-        for (cl_id, _seen) in claim_seen {
-            let possible_id = cl_id.clone();
-            let collided = claim_collision_counts.entry(cl_id);
-            match collided {
-                Entry::Occupied(_) => {}
-                Entry::Vacant(_) => return possible_id,
+        "".to_string()
+    }
+}
+
+impl<'a> Default for Fabric<'a> {
+    fn default() -> Fabric<'a> {
+        Fabric::new()
+    }
+}
+
+/// How many claims cover each part of the fabric.
+///
+/// Rather than a count for every square inch, the fabric is cut along every edge of every claim
+/// into rectangles that are each covered by the same claims throughout. So the counts grow with
+/// the number of claims rather than the size of the fabric, and are never more than one for each
+/// square inch of the area the claims cover, wherever on the fabric they are.
+struct Coverage {
+    /// Where the fabric is cut from top to bottom, sorted and each once.
+    tops: Vec<i64>,
+    /// Where the fabric is cut from left to right, sorted and each once.
+    lefts: Vec<i64>,
+    /// The number of claims covering each rectangle, a row at a time.
+    counts: Vec<u32>,
+}
+
+impl Coverage {
+    fn new(claims: &[Claim]) -> Coverage {
+        let mut tops: Vec<i64> = vec![];
+        let mut lefts: Vec<i64> = vec![];
+        for claim in claims {
+            let (top, left, bottom, right) = claim.edges();
+            tops.extend(&[top, bottom]);
+            lefts.extend(&[left, right]);
+        }
+        tops.sort_unstable();
+        tops.dedup();
+        lefts.sort_unstable();
+        lefts.dedup();
+
+        let mut coverage = Coverage {
+            counts: vec![],
+            tops,
+            lefts,
+        };
+        let width = coverage.lefts.len();
+        let height = coverage.tops.len();
+
+        // mark where each claim starts and stops covering the rectangles at its corners, then a
+        // running total across each row and down each column fills in every rectangle it covers.
+        // The extra row and column at the far edges are only ever stopped in.
+        let mut changes: Vec<i64> = vec![0; width * height];
+        for claim in claims {
+            let (top, left, bottom, right) = claim.edges();
+            let (top, bottom) = (coverage.row_index(top), coverage.row_index(bottom));
+            let (left, right) = (coverage.col_index(left), coverage.col_index(right));
+            changes[top * width + left] += 1;
+            changes[top * width + right] -= 1;
+            changes[bottom * width + left] -= 1;
+            changes[bottom * width + right] += 1;
+        }
+        for row in 0..height {
+            for col in 0..width {
+                let mut total = changes[row * width + col];
+                if row > 0 {
+                    total += changes[(row - 1) * width + col];
+                }
+                if col > 0 {
+                    total += changes[row * width + col - 1];
+                }
+                if row > 0 && col > 0 {
+                    total -= changes[(row - 1) * width + col - 1];
+                }
+                changes[row * width + col] = total;
             }
         }
 
-        return "".to_string();
+        coverage.counts = changes.into_iter().map(|count| count as u32).collect();
+        coverage
+    }
+
+    /// The number of claims covering the rectangle below and to the right of the cuts with these
+    /// indices.
+    fn count(&self, row: usize, col: usize) -> u32 {
+        self.counts[row * self.lefts.len() + col]
+    }
+
+    /// The index of each row of rectangles and how tall it is.
+    fn rows(&self) -> impl Iterator<Item = (usize, i64)> + '_ {
+        self.tops
+            .windows(2)
+            .enumerate()
+            .map(|(row, cut)| (row, cut[1] - cut[0]))
+    }
+
+    /// The index of each column of rectangles and how wide it is.
+    fn cols(&self) -> impl Iterator<Item = (usize, i64)> + '_ {
+        self.lefts
+            .windows(2)
+            .enumerate()
+            .map(|(col, cut)| (col, cut[1] - cut[0]))
+    }
+
+    /// The index of the cut at this distance from the top, which must be the edge of a claim.
+    fn row_index(&self, top: i64) -> usize {
+        self.tops.binary_search(&top).unwrap_or_else(|at| at)
+    }
+
+    /// The index of the cut at this distance from the left, which must be the edge of a claim.
+    fn col_index(&self, left: i64) -> usize {
+        self.lefts.binary_search(&left).unwrap_or_else(|at| at)
     }
 }
 
@@ -100,70 +188,54 @@ impl<'a> Claim<'a> {
     /// Extract a claim from an input line.
     /// The input line looks like: -
     /// #1 @ 662,777: 18x27
-    pub fn from_input_line(input: &'a str) -> Claim<'a> {
-        let mut cl = Claim {
-            id: "",
+    pub fn from_input_line(input: &'a str) -> Result<Claim<'a>, ParseError> {
+        let line_caps = match CLAIMRE.captures(input) {
+            Some(line_caps) => line_caps,
+            None => {
+                return Err(ParseError::new(
+                    input,
+                    "expected a claim like #1 @ 662,777: 18x27",
+                ))
+            }
+        };
+        // The pattern only captures digits so the only way these can fail is by being too large.
+        let number = |group: usize| {
+            line_caps[group]
+                .parse::<i32>()
+                .map_err(|err| ParseError::new(input, format!("{}: {}", &line_caps[group], err)))
+        };
+
+        Ok(Claim {
+            id: line_caps.get(1).map_or("", |id| id.as_str()),
             top_left_position: Coordinate {
-                from_top: 0,
-                from_left: 0,
+                from_left: number(2)?,
+                from_top: number(3)?,
             },
             size: Dimension {
-                width: 0,
-                height: 0,
+                width: number(4)?,
+                height: number(5)?,
             },
-        };
-        let caps = CLAIMRE.captures(input);
-        match caps {
-            Some(line_caps) => {
-                cl.id = line_caps.get(1).unwrap().as_str();
-                let tlx = line_caps.get(2).unwrap().as_str().parse::<i32>().unwrap();
-                let tly = line_caps.get(3).unwrap().as_str().parse::<i32>().unwrap();
-                let top_left = Coordinate {
-                    from_left: tlx,
-                    from_top: tly,
-                };
-
-                let dw = line_caps.get(4).unwrap().as_str().parse::<i32>().unwrap();
-                let dh = line_caps.get(5).unwrap().as_str().parse::<i32>().unwrap();
-                let size = Dimension {
-                    width: dw,
-                    height: dh,
-                };
-
-                cl.top_left_position = top_left;
-                cl.size = size;
-
-                return cl;
-            }
-            None => {}
-        }
-        return cl;
+        })
     }
 
-    /// Provide a list of fabric coordinates required by this claim.
-    fn coordinates(&self) -> Vec<Coordinate> {
-        let mut results: Vec<Coordinate> = vec![];
-
-        for y in
-            self.top_left_position.from_top..(self.top_left_position.from_top + self.size.height)
-        {
-            for x in self.top_left_position.from_left
-                ..(self.top_left_position.from_left + self.size.width)
-            {
-                results.push(Coordinate {
-                    from_left: x,
-                    from_top: y,
-                });
-            }
-        }
-        return results;
+    /// The distances from the top and left of the fabric to the top, left, bottom and right edges
+    /// of this claim. The bottom and right edges are just beyond the last squares claimed.
+    fn edges(&self) -> (i64, i64, i64, i64) {
+        let top = self.top_left_position.from_top as i64;
+        let left = self.top_left_position.from_left as i64;
+        (
+            top,
+            left,
+            top + self.size.height as i64,
+            left + self.size.width as i64,
+        )
     }
 }
 
 #[test]
 fn test_from_input_line() {
     let line = "#1 @ 662,777: 18x27";
-    let cl = Claim::from_input_line(line);
+    let cl = Claim::from_input_line(line).unwrap();
 
     let target = Claim {
         id: "1",
@@ -189,6 +261,38 @@ fn test_from_input_line() {
     assert_eq!(cl.size.width, target.size.width);
 }
 
+#[test]
+fn test_from_input_line_rejects_malformed_claims() {
+    let err = Claim::from_input_line("#1 @ 662,777 18x27").unwrap_err();
+    assert_eq!(err.text, "#1 @ 662,777 18x27");
+    assert_eq!(err.reason, "expected a claim like #1 @ 662,777: 18x27");
+
+    let err = Claim::from_input_line("#1 @ 662,99999999999: 18x27").unwrap_err();
+    assert!(err.reason.starts_with("99999999999: "));
+}
+
+#[cfg(test)]
+/// Provide a list of fabric coordinates required by the claim. Counting the claims on each of
+/// these squares is the slow but obvious way to answer the puzzle, kept as the reference the
+/// fabric is checked against in `test_fabric_matches_counting_every_square`.
+fn coordinates(claim: &Claim) -> Vec<Coordinate> {
+    let mut results: Vec<Coordinate> = vec![];
+
+    for y in
+        claim.top_left_position.from_top..(claim.top_left_position.from_top + claim.size.height)
+    {
+        for x in claim.top_left_position.from_left
+            ..(claim.top_left_position.from_left + claim.size.width)
+        {
+            results.push(Coordinate {
+                from_left: x,
+                from_top: y,
+            });
+        }
+    }
+    results
+}
+
 #[test]
 fn test_claim_coordinates() {
     let cl = Claim {
@@ -202,7 +306,7 @@ fn test_claim_coordinates() {
             height: 4,
         },
     };
-    let stuff = cl.coordinates();
+    let stuff = coordinates(&cl);
     assert_eq!(stuff.len(), 12);
     assert_eq!(
         stuff[0].from_left,
@@ -253,8 +357,70 @@ struct Dimension {
     height: i32,
 }
 
-#[derive(Clone, Debug)]
-/// For a given square inch on the fabric, this records each claim it falls within.
-struct Allocation<'a> {
-    claims: Vec<Claim<'a>>,
+#[test]
+fn test_fabric_example() {
+    let input = ["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 5,5: 2x2"];
+    let mut fabric = Fabric::new();
+    for line in input.iter() {
+        fabric.allocate(Claim::from_input_line(line).unwrap());
+    }
+    assert_eq!(fabric.count_over_allocated_squares(), 4);
+    assert_eq!(fabric.find_claim_without_collisions(), "3");
+}
+
+#[test]
+fn test_fabric_beyond_the_puzzle_size() {
+    // claims far apart only need counts for the area around them.
+    let input = [
+        "#1 @ 2000000000,2000000000: 100x100",
+        "#2 @ 2000000050,2000000099: 100x2",
+        "#3 @ 0,0: 1x1",
+        "#4 @ 5,5: 0x10",
+    ];
+    let mut fabric = Fabric::new();
+    for line in input.iter() {
+        fabric.allocate(Claim::from_input_line(line).unwrap());
+    }
+    assert_eq!(fabric.count_over_allocated_squares(), 50);
+    assert_eq!(fabric.find_claim_without_collisions(), "3");
+}
+
+#[test]
+fn test_fabric_matches_counting_every_square() {
+    use common::generate::{generate, Size};
+    use common::parse::parse_lines;
+    use std::collections::HashMap;
+
+    for seed in 0..5 {
+        let input = generate::<crate::NoMatterHowYouSliceIt>(Size::Records(300), seed);
+        let claims = parse_lines(&input, Claim::from_input_line).unwrap();
+
+        let mut squares: HashMap<(i32, i32), Vec<&str>> = HashMap::new();
+        for claim in &claims {
+            for coord in coordinates(claim) {
+                squares
+                    .entry((coord.from_top, coord.from_left))
+                    .or_default()
+                    .push(claim.id);
+            }
+        }
+        let over_allocated = squares.values().filter(|ids| ids.len() > 1).count() as i64;
+
+        let mut fabric = Fabric::new();
+        for claim in claims.iter().cloned() {
+            fabric.allocate(claim);
+        }
+        assert_eq!(
+            fabric.count_over_allocated_squares(),
+            over_allocated,
+            "seed {}",
+            seed
+        );
+        let alone = fabric.find_claim_without_collisions();
+        if !alone.is_empty() {
+            assert!(squares
+                .values()
+                .all(|ids| !ids.contains(&&alone[..]) || ids.len() == 1));
+        }
+    }
 }
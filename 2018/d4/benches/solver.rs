@@ -0,0 +1,6 @@
+use common::bench::bench_solution;
+use d4::ReposeRecord;
+
+fn main() {
+    bench_solution::<ReposeRecord>("d4");
+}
@@ -1,4 +1,5 @@
 use chrono::prelude::*;
+use common::parse::{parse_lines, ParseError};
 use regex::Regex;
 use std::collections::HashMap;
 
@@ -40,8 +41,10 @@ impl Guard {
         // Update the total sleep seen for this guard.
         self.total_sleep += sleep_end.signed_duration_since(sleep_start).num_minutes();
 
-        // Update the sleep average.
-        self.sleep_average_per_shift = self.total_sleep / self.shifts;
+        // Update the sleep average, there is nothing to average over until a shift has begun.
+        if self.shifts > 0 {
+            self.sleep_average_per_shift = self.total_sleep / self.shifts;
+        }
 
         for m in sleep_start.minute()..sleep_end.minute() {
             let sleep_slot = self.sleep_count_per_minute.entry(m).or_insert(0);
@@ -55,8 +58,8 @@ impl Guard {
         let mut highest: i32 = 0;
         for (k, v) in self.sleep_count_per_minute.iter() {
             if v > &highest {
-                highest = v.clone();
-                result = k.clone();
+                highest = *v;
+                result = *k;
             }
         }
 
@@ -87,8 +90,8 @@ fn test_guard_record_multiple_shifts() {
 fn test_guard_record_shift_and_one_sleep() {
     let mut guard = Guard::new(String::from("1"));
     guard.report_shift();
-    let sleep_start = Utc.ymd(2014, 11, 28).and_hms(12, 0, 9);
-    let sleep_end = Utc.ymd(2014, 11, 28).and_hms(12, 3, 9);
+    let sleep_start = Utc.with_ymd_and_hms(2014, 11, 28, 12, 0, 9).unwrap();
+    let sleep_end = Utc.with_ymd_and_hms(2014, 11, 28, 12, 3, 9).unwrap();
     guard.report_sleep(sleep_start, sleep_end);
     assert_eq!(guard.shifts, 1);
     assert_eq!(guard.sleep_average_per_shift, 3);
@@ -103,11 +106,11 @@ fn test_guard_record_shift_and_one_sleep() {
 fn test_guard_record_shift_and_two_sleeps() {
     let mut guard = Guard::new(String::from("1"));
     guard.report_shift();
-    let sleep_start = Utc.ymd(2014, 11, 28).and_hms(12, 0, 9);
-    let sleep_end = Utc.ymd(2014, 11, 28).and_hms(12, 3, 9);
+    let sleep_start = Utc.with_ymd_and_hms(2014, 11, 28, 12, 0, 9).unwrap();
+    let sleep_end = Utc.with_ymd_and_hms(2014, 11, 28, 12, 3, 9).unwrap();
     guard.report_sleep(sleep_start, sleep_end);
-    let sleep_two_start = Utc.ymd(2014, 11, 28).and_hms(12, 12, 9);
-    let sleep_two_end = Utc.ymd(2014, 11, 28).and_hms(12, 13, 9);
+    let sleep_two_start = Utc.with_ymd_and_hms(2014, 11, 28, 12, 12, 9).unwrap();
+    let sleep_two_end = Utc.with_ymd_and_hms(2014, 11, 28, 12, 13, 9).unwrap();
     guard.report_sleep(sleep_two_start, sleep_two_end);
 
     assert_eq!(guard.shifts, 1);
@@ -124,12 +127,12 @@ fn test_guard_record_shift_and_two_sleeps() {
 fn test_guard_record_two_shifts_and_two_sleeps() {
     let mut guard = Guard::new(String::from("1"));
     guard.report_shift();
-    let sleep_start = Utc.ymd(2014, 11, 28).and_hms(12, 0, 9);
-    let sleep_end = Utc.ymd(2014, 11, 28).and_hms(12, 3, 9);
+    let sleep_start = Utc.with_ymd_and_hms(2014, 11, 28, 12, 0, 9).unwrap();
+    let sleep_end = Utc.with_ymd_and_hms(2014, 11, 28, 12, 3, 9).unwrap();
     guard.report_sleep(sleep_start, sleep_end);
     guard.report_shift();
-    let sleep_two_start = Utc.ymd(2014, 11, 28).and_hms(12, 12, 9);
-    let sleep_two_end = Utc.ymd(2014, 11, 28).and_hms(12, 13, 9);
+    let sleep_two_start = Utc.with_ymd_and_hms(2014, 11, 28, 12, 12, 9).unwrap();
+    let sleep_two_end = Utc.with_ymd_and_hms(2014, 11, 28, 12, 13, 9).unwrap();
     guard.report_sleep(sleep_two_start, sleep_two_end);
 
     assert_eq!(guard.shifts, 2);
@@ -146,12 +149,12 @@ fn test_guard_record_two_shifts_and_two_sleeps() {
 fn test_guard_record_two_shifts_and_two_sleeps_with_overlap() {
     let mut guard = Guard::new(String::from("1"));
     guard.report_shift();
-    let sleep_start = Utc.ymd(2014, 11, 28).and_hms(12, 0, 9);
-    let sleep_end = Utc.ymd(2014, 11, 28).and_hms(12, 3, 9);
+    let sleep_start = Utc.with_ymd_and_hms(2014, 11, 28, 12, 0, 9).unwrap();
+    let sleep_end = Utc.with_ymd_and_hms(2014, 11, 28, 12, 3, 9).unwrap();
     guard.report_sleep(sleep_start, sleep_end);
     guard.report_shift();
-    let sleep_two_start = Utc.ymd(2014, 11, 28).and_hms(12, 2, 9);
-    let sleep_two_end = Utc.ymd(2014, 11, 28).and_hms(12, 4, 9);
+    let sleep_two_start = Utc.with_ymd_and_hms(2014, 11, 28, 12, 2, 9).unwrap();
+    let sleep_two_end = Utc.with_ymd_and_hms(2014, 11, 28, 12, 4, 9).unwrap();
     guard.report_sleep(sleep_two_start, sleep_two_end);
 
     assert_eq!(guard.shifts, 2);
@@ -168,12 +171,12 @@ fn test_guard_record_two_shifts_and_two_sleeps_with_overlap() {
 fn test_guard_frequent_minute() {
     let mut guard = Guard::new(String::from("1"));
     guard.report_shift();
-    let sleep_start = Utc.ymd(2014, 11, 28).and_hms(12, 0, 9);
-    let sleep_end = Utc.ymd(2014, 11, 28).and_hms(12, 3, 9);
+    let sleep_start = Utc.with_ymd_and_hms(2014, 11, 28, 12, 0, 9).unwrap();
+    let sleep_end = Utc.with_ymd_and_hms(2014, 11, 28, 12, 3, 9).unwrap();
     guard.report_sleep(sleep_start, sleep_end);
     guard.report_shift();
-    let sleep_two_start = Utc.ymd(2014, 11, 28).and_hms(12, 2, 9);
-    let sleep_two_end = Utc.ymd(2014, 11, 28).and_hms(12, 4, 9);
+    let sleep_two_start = Utc.with_ymd_and_hms(2014, 11, 28, 12, 2, 9).unwrap();
+    let sleep_two_end = Utc.with_ymd_and_hms(2014, 11, 28, 12, 4, 9).unwrap();
     guard.report_sleep(sleep_two_start, sleep_two_end);
 
     assert_eq!(guard.shifts, 2);
@@ -220,28 +223,37 @@ impl Lab {
     // [1518-09-08 00:51] falls asleep
     // [1518-07-27 00:57] wakes up
     // [1518-10-21 00:00] Guard #2699 begins shift
-    pub fn read_input_into_journal(&mut self, input: String) -> Option<i32> {
+    pub fn read_input_into_journal(&mut self, input: &str) -> Result<i32, ParseError> {
         // Create our journal from the log found on the wall. Once created, we sort this
         // chronologically to make it easier to analyse.
         let mut journal = Journal::new();
-        for line in input.lines() {
-            match JournalEntry::from_input_line(line) {
-                Some(this_entry) => {
-                    journal.entries.push(this_entry);
-                }
-                None => {
-                    return None;
-                }
-            }
-        }
+        let mut numbered: Vec<(usize, JournalEntry)> =
+            parse_lines(input, JournalEntry::from_input_line)?
+                .into_iter()
+                .enumerate()
+                .collect();
 
         // Explicitly sort by just the time field of the JournalEntry to correct the order of the
         // entries.
-        journal.entries.sort_by(|a, b| a.time.cmp(&b.time));
+        numbered.sort_by_key(|(_, a)| a.time);
+
+        // A guard can't sleep or wake before anyone has begun a shift.
+        if let Some((index, entry)) = numbered
+            .first()
+            .filter(|(_, entry)| !SHIFTSTARTRE.is_match(&entry.line))
+        {
+            let line = input.lines().nth(*index).unwrap_or_default();
+            return Err(ParseError::new(
+                line,
+                format!("{} before any guard began a shift", entry.line),
+            )
+            .on_line(index + 1));
+        }
 
+        journal.entries = numbered.into_iter().map(|(_, entry)| entry).collect();
         self.journal = journal;
 
-        return Some(self.journal.entries.len() as i32);
+        Ok(self.journal.entries.len() as i32)
     }
 
     pub fn calculate_guard_sleep_patterns(&mut self) {
@@ -254,17 +266,14 @@ impl Lab {
         let mut sleep_start = Utc::now();
 
         for entry in self.journal.entries.iter() {
-            match SHIFTSTARTRE.captures(entry.line.as_str()) {
-                Some(shift_start_line) => {
-                    guard_id = String::from(shift_start_line.get(1).map_or("", |m| m.as_str()));
-                    let guard = self
-                        .guards
-                        .entry(guard_id.clone())
-                        .or_insert(Guard::new(guard_id.clone()));
-                    guard.report_shift();
-                    continue;
-                }
-                None => {}
+            if let Some(shift_start_line) = SHIFTSTARTRE.captures(entry.line.as_str()) {
+                guard_id = String::from(shift_start_line.get(1).map_or("", |m| m.as_str()));
+                let guard = self
+                    .guards
+                    .entry(guard_id.clone())
+                    .or_insert(Guard::new(guard_id.clone()));
+                guard.report_shift();
+                continue;
             }
 
             if SLEEPSTARTRE.is_match(entry.line.as_str()) {
@@ -297,15 +306,12 @@ impl Lab {
             if v.total_sleep > most_sleep {
                 guard_id = v.id.clone();
                 most_sleep = v.total_sleep;
-                match v.frequent_sleep_minute() {
-                    Some(target_minute) => {
-                        peak_minute = target_minute;
-                    }
-                    None => {}
+                if let Some(target_minute) = v.frequent_sleep_minute() {
+                    peak_minute = target_minute;
                 }
             }
         }
-        return (guard_id, peak_minute);
+        (guard_id, peak_minute)
     }
 
     /// Find the guard that most regularly sleeps for a particular minute.
@@ -314,18 +320,21 @@ impl Lab {
         let mut peak_per_minute: i32 = 0;
         let mut peak_minute: u32 = 0;
         for (_k, v) in self.guards.iter() {
-            match v.frequent_sleep_minute() {
-                Some(target_minute) => {
-                    if v.sleep_count_per_minute[&target_minute] > peak_per_minute {
-                        guard_id = v.id.clone();
-                        peak_minute = target_minute;
-                        peak_per_minute = v.sleep_count_per_minute[&target_minute];
-                    }
+            if let Some(target_minute) = v.frequent_sleep_minute() {
+                if v.sleep_count_per_minute[&target_minute] > peak_per_minute {
+                    guard_id = v.id.clone();
+                    peak_minute = target_minute;
+                    peak_per_minute = v.sleep_count_per_minute[&target_minute];
                 }
-                None => {}
             }
         }
-        return (guard_id, peak_minute);
+        (guard_id, peak_minute)
+    }
+}
+
+impl Default for Lab {
+    fn default() -> Lab {
+        Lab::new()
     }
 }
 
@@ -345,24 +354,39 @@ impl JournalEntry {
     }
 
     /// create a JournalEntry from an individual line found in the input.
-    fn from_input_line(line: &str) -> Option<JournalEntry> {
-        let this_line = String::from(line);
-        let line_parts: Vec<&str> = this_line.split(|c| c == ']' || c == '[').collect();
+    fn from_input_line(line: &str) -> Result<JournalEntry, ParseError> {
+        let (stamp, event) = match line.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+            Some(parts) => parts,
+            None => {
+                return Err(ParseError::new(
+                    line,
+                    "expected an entry like [1518-05-18 00:01] falls asleep",
+                ))
+            }
+        };
 
         let mut this_entry = JournalEntry::new();
-        match Utc.datetime_from_str(line_parts[1], "%Y-%m-%d %H:%M") {
+        match NaiveDateTime::parse_from_str(stamp, "%Y-%m-%d %H:%M") {
             Ok(the_time) => {
-                this_entry.time = the_time;
+                this_entry.time = Utc.from_utc_datetime(&the_time);
             }
             Err(the_err) => {
-                println!("failed to parse time: {}", the_err.to_string());
-                return None;
+                return Err(ParseError::new(
+                    line,
+                    format!("failed to parse time: {}", the_err),
+                ));
             }
         }
 
-        this_entry.line = line_parts[2].trim_left().to_string();
+        this_entry.line = event.trim_start().to_string();
+        if !SHIFTSTARTRE.is_match(&this_entry.line)
+            && !SLEEPSTARTRE.is_match(&this_entry.line)
+            && !SLEEPENDRE.is_match(&this_entry.line)
+        {
+            return Err(ParseError::new(line, "unknown event"));
+        }
 
-        return Some(this_entry);
+        Ok(this_entry)
     }
 }
 
@@ -371,13 +395,51 @@ fn test_journal_entry_from_input_line() {
     let input_line = "[1518-05-18 00:01] Guard #1171 begins shift";
     assert_eq!(
         JournalEntry::from_input_line(input_line),
-        Some(JournalEntry {
+        Ok(JournalEntry {
             time: "1518-05-18T00:01:00Z".parse::<DateTime<Utc>>().unwrap(),
             line: String::from("Guard #1171 begins shift"),
         })
     )
 }
 
+#[test]
+fn test_journal_entry_from_malformed_line() {
+    let err =
+        JournalEntry::from_input_line("1518-05-18 00:01 Guard #1171 begins shift").unwrap_err();
+    assert_eq!(
+        err.reason,
+        "expected an entry like [1518-05-18 00:01] falls asleep"
+    );
+
+    let err = JournalEntry::from_input_line("[1518-05-18 00:01] dozes off").unwrap_err();
+    assert_eq!(err.reason, "unknown event");
+}
+
+#[test]
+fn test_journal_sleeping_before_any_shift() {
+    let mut lab = Lab::new();
+    let err = lab
+        .read_input_into_journal(
+            "[1518-01-01 00:10] wakes up\n\
+             [1518-01-01 00:05] falls asleep\n\
+             [1518-01-01 00:20] Guard #10 begins shift",
+        )
+        .unwrap_err();
+    assert_eq!(err.line, 2);
+    assert_eq!(err.text, "[1518-01-01 00:05] falls asleep");
+    assert_eq!(err.reason, "falls asleep before any guard began a shift");
+}
+
+#[test]
+fn test_guard_record_sleep_without_shift() {
+    let mut guard = Guard::new(String::from("1"));
+    let sleep_start = Utc.with_ymd_and_hms(2014, 11, 28, 12, 0, 9).unwrap();
+    let sleep_end = Utc.with_ymd_and_hms(2014, 11, 28, 12, 3, 9).unwrap();
+    guard.report_sleep(sleep_start, sleep_end);
+    assert_eq!(guard.total_sleep, 3);
+    assert_eq!(guard.sleep_average_per_shift, 0);
+}
+
 #[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
 /// The complete journal as read from the wall. This enables sorting of the journal entries by time.
 struct Journal {
@@ -0,0 +1,102 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod closet;
+
+use chrono::{Duration, NaiveDate};
+use common::generate::{Generate, Rng};
+use common::input::Input;
+use common::parse::ParseError;
+use common::solution::{Answer, Solution};
+
+/// Day 4: Repose Record.
+pub struct ReposeRecord;
+
+impl Solution for ReposeRecord {
+    type Parsed<'a> = closet::Lab;
+
+    /// Read the journal from the input and work out each guard's sleeping habits.
+    fn parse(input: &Input) -> Result<closet::Lab, ParseError> {
+        let mut secret_lab = closet::Lab::new();
+        if secret_lab.read_input_into_journal(input.as_str())? == 0 {
+            return Err(ParseError::new("", "didn't read any entries"));
+        }
+        secret_lab.calculate_guard_sleep_patterns();
+        Ok(secret_lab)
+    }
+
+    /// The ID of the guard who sleeps the most multiplied by the minute they are most often asleep.
+    fn part1(secret_lab: &closet::Lab) -> Answer {
+        let (sleepy_guard, sleep_minute) = secret_lab.target_guard_and_minute();
+        guard_answer(&sleepy_guard, sleep_minute)
+    }
+
+    /// The ID of the guard most frequently asleep on the same minute multiplied by that minute.
+    fn part2(secret_lab: &closet::Lab) -> Answer {
+        let (regular_sleepy_guard, regular_sleep_minute) =
+            secret_lab.target_regular_sleeping_guard_and_minute();
+        guard_answer(&regular_sleepy_guard, regular_sleep_minute)
+    }
+}
+
+/// The guard's ID multiplied by the minute. A journal where nobody ever falls asleep has no guard
+/// to pick, so there's no answer.
+fn guard_answer(guard: &str, minute: u32) -> Answer {
+    match guard.parse::<i64>() {
+        Ok(id) => (id * minute as i64).into(),
+        Err(_) => Answer::Unsolved,
+    }
+}
+
+impl Generate for ReposeRecord {
+    /// The number of entries in the journal.
+    const REAL_SIZE: usize = 1040;
+
+    /// A shift a night, each starting a little before or after midnight and with a few naps before
+    /// the hour is up. The entries are shuffled as the real journal is.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let guards: Vec<i64> = (0..size / 40 + 3).map(|_| rng.between(10, 3500)).collect();
+        let first_night = NaiveDate::from_ymd_opt(1518, 1, 1).unwrap_or_default();
+        let mut entries: Vec<String> = vec![];
+
+        let mut night = 0;
+        while entries.len() < size {
+            let date = first_night + Duration::days(night);
+            night += 1;
+            let starts = if rng.one_in(3) {
+                format!("{} 23:{}", date - Duration::days(1), rng.between(45, 59))
+            } else {
+                format!("{} 00:{:02}", date, rng.between(0, 4))
+            };
+            entries.push(format!(
+                "[{}] Guard #{} begins shift",
+                starts,
+                rng.pick(&guards)
+            ));
+
+            let mut minute = rng.between(5, 20);
+            for _ in 0..rng.between(0, 3) {
+                let wakes = minute + rng.between(1, 20);
+                if wakes > 59 || entries.len() + 2 > size {
+                    break;
+                }
+                entries.push(format!("[{} 00:{:02}] falls asleep", date, minute));
+                entries.push(format!("[{} 00:{:02}] wakes up", date, wakes));
+                minute = wakes + rng.between(1, 10);
+            }
+        }
+
+        rng.shuffle(&mut entries);
+        entries.iter().map(|entry| format!("{}\n", entry)).collect()
+    }
+}
+
+#[test]
+fn test_generated_input_parses() {
+    use common::generate::{generate, Size};
+
+    let input = Input::new(&generate::<ReposeRecord>(Size::Records(300), 4));
+    assert_eq!(input.lines().count(), 300);
+    let secret_lab = ReposeRecord::parse(&input).unwrap();
+    assert_ne!(ReposeRecord::part1(&secret_lab), Answer::Unsolved);
+}
@@ -0,0 +1,6 @@
+use common::bench::bench_solution;
+use d5::AlchemicalReduction;
+
+fn main() {
+    bench_solution::<AlchemicalReduction>("d5");
+}
@@ -26,10 +26,10 @@ impl Polymer {
     /// Refine the polymer by reacting all the units in the raw polymer. This reaction cascades
     /// until there are no reactions left.
     pub fn refine(&mut self) -> String {
-        while self.refined == false {
+        while !self.refined {
             self.react();
         }
-        return self.last.clone();
+        self.last.clone()
     }
 
     /// Perform a single round of reactions on the polymer.
@@ -67,7 +67,7 @@ impl Polymer {
     pub fn optimum_refine_by_dropping_a_unit(&mut self) -> String {
         self.shortest = self.last.len();
 
-        for num in 97..97 + 26 as u8 {
+        for num in 97..97 + 26_u8 {
             let lower = format!("{}", num as char);
             let upper = lower.to_uppercase();
 
@@ -84,7 +84,7 @@ impl Polymer {
             }
         }
 
-        return self.shortest_last.clone();
+        self.shortest_last.clone()
     }
 }
 
@@ -123,45 +123,45 @@ fn chars_react(a: char, b: char) -> bool {
         spacing = bnum - anum;
     }
 
-    return spacing == CHAR_SPACE;
+    spacing == CHAR_SPACE
 }
 
 #[test]
 fn test_chars_react_matches() {
     let lower = 'a';
     let upper = 'A';
-    assert_eq!(chars_react(lower, upper), true);
-    assert_eq!(chars_react(upper, lower), true);
+    assert!(chars_react(lower, upper));
+    assert!(chars_react(upper, lower));
 }
 
 #[test]
 fn test_chars_react_pair_another_letter() {
     let lower = 'z';
     let upper = 'Z';
-    assert_eq!(chars_react(lower, upper), true);
-    assert_eq!(chars_react(upper, lower), true);
+    assert!(chars_react(lower, upper));
+    assert!(chars_react(upper, lower));
 }
 
 #[test]
 fn test_chars_react_non_reacting_pair() {
     let lower = 'a';
     let upper = 'B';
-    assert_eq!(chars_react(lower, upper), false);
-    assert_eq!(chars_react(upper, lower), false);
+    assert!(!chars_react(lower, upper));
+    assert!(!chars_react(upper, lower));
 }
 
 #[test]
 fn test_chars_react_non_reacting_pair_but_one() {
     let lower = 'a';
     let upper = 'C';
-    assert_eq!(chars_react(lower, upper), false);
-    assert_eq!(chars_react(upper, lower), false);
+    assert!(!chars_react(lower, upper));
+    assert!(!chars_react(upper, lower));
 }
 
 #[test]
 fn test_chars_react_non_reacting_same_letter() {
     let lower = 'a';
     let upper = 'a';
-    assert_eq!(chars_react(lower, upper), false);
-    assert_eq!(chars_react(upper, lower), false);
+    assert!(!chars_react(lower, upper));
+    assert!(!chars_react(upper, lower));
 }
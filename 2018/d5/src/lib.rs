@@ -0,0 +1,81 @@
+pub mod lab;
+
+use common::generate::{Generate, Rng};
+use common::input::Input;
+use common::parse::{parse_lines, ParseError};
+use common::solution::{Answer, Solution};
+
+/// Day 5: Alchemical Reduction.
+pub struct AlchemicalReduction;
+
+impl Solution for AlchemicalReduction {
+    type Parsed<'a> = &'a str;
+
+    /// The polymer is a single line of units, each one a letter.
+    fn parse(input: &Input) -> Result<&str, ParseError> {
+        parse_lines(input.as_str(), |line| {
+            match line.chars().find(|c| !c.is_ascii_alphabetic()) {
+                Some(unit) => Err(ParseError::new(line, format!("{:?} isn't a unit", unit))),
+                None => Ok(()),
+            }
+        })?;
+        if let Some(extra) = input.lines().nth(1) {
+            return Err(
+                ParseError::new(extra, "the polymer should be on a single line").on_line(2),
+            );
+        }
+        Ok(input.as_str())
+    }
+
+    /// Number of units left once the polymer has fully reacted.
+    fn part1(raw: &&str) -> Answer {
+        lab::Polymer::new(raw).refine().len().into()
+    }
+
+    /// Length of the shortest polymer possible by removing every unit of one type.
+    fn part2(raw: &&str) -> Answer {
+        let mut p = lab::Polymer::new(raw);
+        p.refine();
+        p.optimum_refine_by_dropping_a_unit().len().into()
+    }
+}
+
+impl Generate for AlchemicalReduction {
+    /// The number of units in the polymer.
+    const REAL_SIZE: usize = 50000;
+
+    /// Units of any type in either polarity. Every so often a unit is followed by its opposite so
+    /// there is plenty for the polymer to react away.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let units: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        let mut polymer = String::with_capacity(size);
+        let mut last = *rng.pick(&units);
+        for _ in 0..size {
+            last = if !rng.one_in(3) {
+                *rng.pick(&units)
+            } else if last.is_ascii_lowercase() {
+                last.to_ascii_uppercase()
+            } else {
+                last.to_ascii_lowercase()
+            };
+            polymer.push(last);
+        }
+        polymer
+    }
+}
+
+#[test]
+fn test_parse_rejects_other_characters() {
+    let err = AlchemicalReduction::parse(&Input::new("dabAc1CaCBAcCcaDA")).unwrap_err();
+    assert_eq!(err.line, 1);
+    assert_eq!(err.reason, "'1' isn't a unit");
+}
+
+#[test]
+fn test_generated_input_parses() {
+    use common::generate::{generate, Size};
+
+    let input = Input::new(&generate::<AlchemicalReduction>(Size::Records(1000), 5));
+    let polymer = AlchemicalReduction::parse(&input).unwrap();
+    assert_eq!(polymer.len(), 1000);
+}
@@ -0,0 +1,6 @@
+use common::bench::bench_solution;
+use d6::ChronalCoordinates;
+
+fn main() {
+    bench_solution::<ChronalCoordinates>("d6");
+}
@@ -1,3 +1,4 @@
+use common::parse::{parse_lines, ParseError};
 use std::collections::HashMap;
 use std::fmt;
 
@@ -49,21 +50,25 @@ impl Coordinates {
 
     /// The input consists of coordinate pairs on each line. These points are stored and given a
     /// unique index.
-    pub fn populate_from_input(&mut self, input: &str) {
-        let mut count: u16 = 1;
-
-        for line in input.lines() {
-            let parts: Vec<&str> = line.split(',').collect();
-            let this_point = Point {
-                id: count,
-                x: parts[0].parse::<i32>().unwrap(),
-                y: parts[1].trim_left().parse::<i32>().unwrap(),
-                finite: false,
-                allocations: 0,
+    pub fn populate_from_input(&mut self, input: &str) -> Result<(), ParseError> {
+        let points = parse_lines(input, |line| {
+            let (x, y) = match line.split_once(',') {
+                Some(parts) => parts,
+                None => return Err(ParseError::new(line, "expected a coordinate like 1, 6")),
             };
+            let value = |part: &str| match part.trim().parse::<i32>() {
+                Ok(v) if v < 0 => Err(ParseError::new(line, "coordinates can't be negative")),
+                Ok(v) => Ok(v),
+                Err(err) => Err(ParseError::new(line, format!("{:?}: {}", part.trim(), err))),
+            };
+            Ok(Point::new(value(x)?, value(y)?))
+        })?;
+
+        for (count, mut this_point) in (1..).zip(points) {
+            this_point.id = count;
             self.points.insert(count, this_point);
-            count += 1;
         }
+        Ok(())
     }
 
     /// Work through all the points and find the maximum x and y coordinates required.
@@ -73,14 +78,14 @@ impl Coordinates {
 
         for (_k, p) in self.points.iter() {
             if p.x > max_x {
-                max_x = p.x.clone()
+                max_x = p.x
             }
             if p.y > max_y {
-                max_y = p.y.clone()
+                max_y = p.y
             }
         }
 
-        return (max_x, max_y);
+        (max_x, max_y)
     }
 
     /// Sum up the Manhattan distances of the provided point to all the locations we have.
@@ -95,6 +100,12 @@ impl Coordinates {
     }
 }
 
+impl Default for Coordinates {
+    fn default() -> Coordinates {
+        Coordinates::new()
+    }
+}
+
 #[test]
 fn test_minimum_bounding_box() {
     let mut g = Coordinates::new();
@@ -166,7 +177,7 @@ impl Grid {
                 // nearest points.
                 if self.is_on_perimiter(x, y) {
                     for id in self.matrix[y][x].nearest.iter() {
-                        allocated_on_edge.push(id.clone());
+                        allocated_on_edge.push(*id);
                     }
                 }
 
@@ -201,12 +212,7 @@ impl Grid {
     fn is_on_perimiter(&self, x: usize, y: usize) -> bool {
         let (max_x, max_y) = self.coords.minimum_bounding_box_dimension();
 
-        if x == 0 || x == max_x as usize {
-            return true;
-        } else if y == 0 || y == max_y as usize {
-            return true;
-        }
-        return false;
+        x == 0 || x == max_x as usize || y == 0 || y == max_y as usize
     }
 
     /// Once all the allocating has been performed, we can check what the largest finite allocation
@@ -220,7 +226,7 @@ impl Grid {
             }
         }
 
-        return max_seen;
+        max_seen
     }
 }
 
@@ -245,6 +251,20 @@ fn distance_between_points(a: Point, b: Point) -> i32 {
     (a.x - b.x).abs() + (a.y - b.y).abs()
 }
 
+#[test]
+fn test_populate_from_input() {
+    let mut coords = Coordinates::new();
+    assert_eq!(coords.populate_from_input("1, 1\n1, 6\n8, 3"), Ok(()));
+    assert_eq!(coords.points.len(), 3);
+    assert_eq!((coords.points[&2].x, coords.points[&2].y), (1, 6));
+
+    let err = Coordinates::new()
+        .populate_from_input("1, 1\n1 6")
+        .unwrap_err();
+    assert_eq!(err.line, 2);
+    assert_eq!(err.reason, "expected a coordinate like 1, 6");
+}
+
 #[test]
 fn test_distance_between_points() {
     let a1 = Point::new(1, 1);
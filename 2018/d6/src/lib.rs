@@ -0,0 +1,63 @@
+pub mod grid;
+
+use common::generate::{Generate, Rng};
+use common::input::Input;
+use common::parse::ParseError;
+use common::solution::{Answer, Solution};
+use std::collections::HashSet;
+
+/// Day 6: Chronal Coordinates.
+pub struct ChronalCoordinates;
+
+impl Solution for ChronalCoordinates {
+    type Parsed<'a> = grid::Grid;
+
+    /// Allocate every location on the grid to its nearest coordinate.
+    fn parse(input: &Input) -> Result<grid::Grid, ParseError> {
+        let mut coords = grid::Coordinates::new();
+        coords.populate_from_input(input.as_str())?;
+        let mut g = grid::Grid::new(coords);
+        g.allocate_matrix_points();
+        Ok(g)
+    }
+
+    /// Size of the largest area that isn't infinite.
+    fn part1(g: &grid::Grid) -> Answer {
+        g.max_allocations_for_finite_point().into()
+    }
+
+    /// Size of the region within the safe total distance of every coordinate.
+    fn part2(g: &grid::Grid) -> Answer {
+        g.qualifying_locations.into()
+    }
+}
+
+impl Generate for ChronalCoordinates {
+    const REAL_SIZE: usize = 50;
+
+    /// Distinct coordinates kept clear of the edges. The area they are spread over grows with their
+    /// number so they are about as far apart as in the real input.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let scale = (size as f64 / Self::REAL_SIZE as f64).sqrt().max(1.0);
+        let side = (400.0 * scale) as i64;
+        let mut seen = HashSet::new();
+        let mut input = String::new();
+        while seen.len() < size {
+            let coordinate = (rng.between(40, side - 40), rng.between(40, side - 40));
+            if seen.insert(coordinate) {
+                input.push_str(&format!("{}, {}\n", coordinate.0, coordinate.1));
+            }
+        }
+        input
+    }
+}
+
+#[test]
+fn test_generated_input_parses() {
+    use common::generate::{generate, Size};
+
+    let input = Input::new(&generate::<ChronalCoordinates>(Size::Records(20), 6));
+    assert_eq!(input.lines().count(), 20);
+    let g = ChronalCoordinates::parse(&input).unwrap();
+    assert!(g.max_allocations_for_finite_point() > 0);
+}
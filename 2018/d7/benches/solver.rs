@@ -0,0 +1,6 @@
+use common::bench::bench_solution;
+use d7::SumOfItsParts;
+
+fn main() {
+    bench_solution::<SumOfItsParts>("d7");
+}
@@ -0,0 +1,118 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod sequencer;
+
+use common::generate::{Generate, Rng};
+use common::input::Input;
+use common::parse::ParseError;
+use common::solution::{Answer, Solution};
+use std::collections::HashSet;
+
+/// Day 7: The Sum of Its Parts.
+pub struct SumOfItsParts;
+
+impl Solution for SumOfItsParts {
+    type Parsed<'a> = sequencer::Sequencer;
+
+    fn parse(input: &Input) -> Result<sequencer::Sequencer, ParseError> {
+        sequencer::Sequencer::new_from_input(input.as_str())
+    }
+
+    /// Order in which the steps of the instructions should be completed.
+    fn part1(instructions: &sequencer::Sequencer) -> Answer {
+        instructions.clone().sequence().into()
+    }
+
+    /// Seconds five workers take to complete every step, each step taking 60 seconds plus its
+    /// position in the alphabet.
+    fn part2(instructions: &sequencer::Sequencer) -> Answer {
+        instructions.assembly_time(5, 60).into()
+    }
+}
+
+impl Generate for SumOfItsParts {
+    /// The number of dependencies between steps.
+    const REAL_SIZE: usize = 101;
+
+    /// Dependencies between lettered steps, 26 as in the real input or a step for every four
+    /// dependencies when there are more. Past `Z` the steps are named `AA`, `AB` and so on. The
+    /// steps are put in a random order first and a step only ever depends on one earlier in it, so
+    /// the steps can always be completed.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut steps: Vec<String> = (0..26.max(size.div_ceil(4))).map(step_name).collect();
+        rng.shuffle(&mut steps);
+
+        // with at least 26 steps there are always more than four pairs of them for every step.
+        let mut dependencies: Vec<(usize, usize)> = vec![];
+        let mut seen: HashSet<(usize, usize)> = HashSet::new();
+        while dependencies.len() < size {
+            let first = rng.below(steps.len() as u64) as usize;
+            let second = rng.below(steps.len() as u64) as usize;
+            let pair = (first.min(second), first.max(second));
+            if first != second && seen.insert(pair) {
+                dependencies.push(pair);
+            }
+        }
+
+        let mut input = String::new();
+        for (before, after) in dependencies {
+            input.push_str(&format!(
+                "Step {} must be finished before step {} can begin.\n",
+                steps[before], steps[after]
+            ));
+        }
+        input
+    }
+}
+
+/// The name of a step counting from `A` to `Z`, then `AA` to `ZZ` and on, as spreadsheet columns
+/// are named.
+fn step_name(index: usize) -> String {
+    let mut name = vec![];
+    let mut rest = index + 1;
+    while rest > 0 {
+        rest -= 1;
+        name.push((b'A' + (rest % 26) as u8) as char);
+        rest /= 26;
+    }
+    name.iter().rev().collect()
+}
+
+#[test]
+fn test_step_name() {
+    assert_eq!(step_name(0), "A");
+    assert_eq!(step_name(25), "Z");
+    assert_eq!(step_name(26), "AA");
+    assert_eq!(step_name(27 * 26 - 1), "ZZ");
+    assert_eq!(step_name(27 * 26), "AAA");
+}
+
+#[test]
+fn test_generated_input_parses() {
+    use common::generate::{generate, Size};
+
+    let input = Input::new(&generate::<SumOfItsParts>(Size::Scale(1), 7));
+    assert_eq!(input.lines().count(), 101);
+    let instructions = SumOfItsParts::parse(&input).unwrap();
+    match SumOfItsParts::part1(&instructions) {
+        Answer::Text(order) => assert_eq!(order.len(), 26),
+        answer => panic!("unexpected answer {:?}", answer),
+    }
+}
+
+#[test]
+fn test_generated_input_grows_with_scale() {
+    use common::generate::{generate, Size};
+
+    let input = Input::new(&generate::<SumOfItsParts>(Size::Scale(100), 7));
+    assert_eq!(input.lines().count(), 10100);
+
+    let input = Input::new(&generate::<SumOfItsParts>(Size::Scale(10), 7));
+    assert_eq!(input.lines().count(), 1010);
+    let instructions = SumOfItsParts::parse(&input).unwrap();
+    match SumOfItsParts::part1(&instructions) {
+        Answer::Text(order) => assert!(order.len() > 26 * 2),
+        answer => panic!("unexpected answer {:?}", answer),
+    }
+}
@@ -1,38 +1,16 @@
-use std::fs::File;
-use std::io::prelude::*;
-use std::path::Path;
-
-mod sequencer;
-
-extern crate regex;
-
-#[macro_use]
-extern crate lazy_static;
+use common::input::Input;
+use common::solution::print_answers;
+use d7::SumOfItsParts;
+use std::process;
 
 fn main() {
-    let mut seq = sequencer::Sequencer::new_from_input(read_input("input").as_str());
-    println!("{}", seq.sequence());
-}
-
-/// This seems to be a common pattern for these puzzles.
-fn read_input(file_name: &str) -> String {
-    let path = Path::new(file_name);
-
-    // Open the path in read-only mode, returns `io::Result<File>`
-    let mut file = match File::open(&path) {
-        // The `description` method of `io::Error` returns a string that
-        // describes the error
-        Err(why) => panic!("couldn't open {}: {}", file_name, why),
-        Ok(file) => file,
-    };
-
-    let contents: &mut String = &mut String::new();
-
-    // Read the file contents into a string, returns `io::Result<usize>`
-    match file.read_to_string(contents) {
-        Err(why) => panic!("couldn't read {}: {}", file_name, why),
-        Ok(_) => println!("{} read", file_name),
+    let input = Input::from_args().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+
+    if let Err(err) = print_answers::<SumOfItsParts>(&input) {
+        eprintln!("{}", err);
+        process::exit(1);
     }
-
-    return contents.clone();
 }
@@ -1,8 +1,10 @@
+use common::parse::{parse_lines, ParseError};
 use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
 
 lazy_static! {
     static ref PAIRRE: Regex =
-        Regex::new(r"^Step\s(?P<child>\w)\s.*\sstep\s(?P<parent>\w)\s.*$").unwrap();
+        Regex::new(r"^Step\s(?P<child>\w+)\s.*\sstep\s(?P<parent>\w+)\s.*$").unwrap();
 }
 
 #[derive(Debug, Clone)]
@@ -18,26 +20,37 @@ impl Pair {
     /// The line looks like: -
     ///
     /// `Step F must be finished before step N can begin.`
-    pub fn new_from_line(line: &str) -> Pair {
-        let caps = PAIRRE.captures(line).unwrap();
-        let parent = caps.name("parent").unwrap().as_str();
-        let child = caps.name("child").unwrap().as_str();
-
-        Pair {
-            parent: parent.to_string(),
-            child: child.to_string(),
+    pub fn new_from_line(line: &str) -> Result<Pair, ParseError> {
+        match PAIRRE.captures(line) {
+            Some(caps) => Ok(Pair {
+                parent: caps["parent"].to_string(),
+                child: caps["child"].to_string(),
+            }),
+            None => Err(ParseError::new(
+                line,
+                "expected a step like Step F must be finished before step N can begin.",
+            )),
         }
     }
 }
 
 #[test]
 fn test_new_pair_from_line() {
-    let p = Pair::new_from_line("Step F must be finished before step N can begin.");
+    let p = Pair::new_from_line("Step F must be finished before step N can begin.").unwrap();
     assert_eq!("N".to_string(), p.parent);
     assert_eq!("F".to_string(), p.child);
+    let p = Pair::new_from_line("Step AB must be finished before step Z can begin.").unwrap();
+    assert_eq!("Z".to_string(), p.parent);
+    assert_eq!("AB".to_string(), p.child);
 }
 
-#[derive(Debug)]
+#[test]
+fn test_new_pair_from_malformed_line() {
+    let err = Pair::new_from_line("Step F before step N").unwrap_err();
+    assert_eq!(err.text, "Step F before step N");
+}
+
+#[derive(Debug, Clone)]
 /// Sequencer performs the following basic algorithm
 ///
 /// Extract pairs of steps from puzzle input to obtain a list of step dependencies
@@ -55,36 +68,46 @@ pub struct Sequencer {
 impl Sequencer {
     /// Read the lines from the input of instruction sequences and build the dependency graph. This
     /// creates a sequencer that understands the relationship between each instruction step.
-    pub fn new_from_input(input: &str) -> Sequencer {
-        let mut uncompleted_steps: Vec<Pair> = vec![];
+    ///
+    /// Steps that depend on each other in a loop could never be completed, so they are rejected.
+    pub fn new_from_input(input: &str) -> Result<Sequencer, ParseError> {
+        let uncompleted_steps: Vec<Pair> = parse_lines(input, Pair::new_from_line)?;
         let completed_steps: Vec<String> = vec![];
-        for line in input.lines() {
-            let uncompleted_step = Pair::new_from_line(&line);
-            uncompleted_steps.push(uncompleted_step);
+
+        let stuck = never_begin(&uncompleted_steps);
+        if !stuck.is_empty() {
+            return Err(ParseError::new(
+                "",
+                format!(
+                    "steps {} can never begin as they depend on each other in a loop",
+                    stuck.join(", ")
+                ),
+            ));
         }
 
-        Sequencer {
+        Ok(Sequencer {
             uncompleted_steps,
             completed_steps,
-        }
+        })
     }
 
     pub fn sequence(&mut self) -> String {
         while !self.uncompleted_steps.is_empty() {
             let next_steps = self.available_steps();
-            if next_steps.len() > 0 {
+            if !next_steps.is_empty() {
                 self.completed_steps.push(String::from(&next_steps[0]));
                 let mut still_uncompleted_steps: Vec<Pair> = vec![];
                 for incomplete_step in &self.uncompleted_steps {
                     if !incomplete_step.child.eq(&next_steps[0]) {
-                        still_uncompleted_steps.push(Pair{
+                        still_uncompleted_steps.push(Pair {
                             parent: String::from(&incomplete_step.parent),
-                            child: String::from(&incomplete_step.child)
+                            child: String::from(&incomplete_step.child),
                         })
                     }
                 }
                 if self.uncompleted_steps.len() == 1 {
-                    self.completed_steps.push(String::from(&self.uncompleted_steps[0].parent))
+                    self.completed_steps
+                        .push(String::from(&self.uncompleted_steps[0].parent))
                 }
                 self.uncompleted_steps = still_uncompleted_steps
             }
@@ -109,7 +132,7 @@ impl Sequencer {
 
         for child_step in child_steps {
             if !parent_steps.contains(&child_step) && !results.contains(&child_step) {
-                results.push(String::from(child_step))
+                results.push(child_step)
             }
         }
         results.sort();
@@ -117,6 +140,121 @@ impl Sequencer {
     }
 }
 
+impl Sequencer {
+    /// The seconds it takes the workers to complete every step, each taking `base_duration`
+    /// seconds plus the position of its name in the alphabet. Whenever a worker is free it starts
+    /// on the first available step alphabetically, a step being available once every step it
+    /// depends on is complete.
+    pub fn assembly_time(&self, workers: usize, base_duration: usize) -> usize {
+        let mut waiting_on: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut released_by: HashMap<&str, Vec<&str>> = HashMap::new();
+        for pair in &self.uncompleted_steps {
+            *waiting_on.entry(&pair.parent).or_insert(0) += 1;
+            waiting_on.entry(&pair.child).or_insert(0);
+            released_by
+                .entry(&pair.child)
+                .or_default()
+                .push(&pair.parent);
+        }
+
+        let mut now = 0;
+        let mut in_progress: Vec<(usize, &str)> = vec![];
+        loop {
+            let mut available: Vec<&str> = waiting_on
+                .iter()
+                .filter(|(_, count)| **count == 0)
+                .map(|(step, _)| *step)
+                .take(workers - in_progress.len())
+                .collect();
+            for step in available.drain(..) {
+                waiting_on.remove(step);
+                in_progress.push((now + base_duration + step_duration(step), step));
+            }
+
+            // the next to finish frees up a worker and may release the steps waiting on it.
+            now = match in_progress.iter().map(|(finish, _)| *finish).min() {
+                Some(finish) => finish,
+                None => return now,
+            };
+            for (_, step) in in_progress.iter().filter(|(finish, _)| *finish == now) {
+                for released in released_by.get(step).into_iter().flatten() {
+                    if let Some(count) = waiting_on.get_mut(released) {
+                        *count -= 1;
+                    }
+                }
+            }
+            in_progress.retain(|(finish, _)| *finish != now);
+        }
+    }
+}
+
+/// The position of the step's name in the alphabet, `A` being 1 and `Z` 26. Longer names carry on
+/// from there, `AA` being 27, in the way spreadsheet columns are counted.
+fn step_duration(step: &str) -> usize {
+    step.chars()
+        .filter(|c| c.is_ascii_uppercase())
+        .fold(0, |total, c| total * 26 + (c as usize - 'A' as usize + 1))
+}
+
+#[test]
+fn test_assembly_time_using_example() {
+    let input = "Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.";
+    let seq = Sequencer::new_from_input(input).unwrap();
+    assert_eq!(seq.assembly_time(2, 0), 15);
+    assert_eq!(seq.assembly_time(1, 0), 21);
+}
+
+#[test]
+fn test_step_duration() {
+    assert_eq!(step_duration("A"), 1);
+    assert_eq!(step_duration("Z"), 26);
+    assert_eq!(step_duration("AB"), 28);
+}
+
+/// The steps that can never begin, in alphabetical order. Steps with nothing left to wait on are
+/// taken out one at a time, releasing the steps that wait on them, and any left over once none
+/// can be taken out wait on a loop.
+fn never_begin(pairs: &[Pair]) -> Vec<String> {
+    let mut waiting_on: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut released_by: HashMap<&str, Vec<&str>> = HashMap::new();
+    for pair in pairs {
+        *waiting_on.entry(&pair.parent).or_insert(0) += 1;
+        waiting_on.entry(&pair.child).or_insert(0);
+        released_by
+            .entry(&pair.child)
+            .or_default()
+            .push(&pair.parent);
+    }
+
+    let mut ready: Vec<&str> = waiting_on
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(step, _)| *step)
+        .collect();
+    while let Some(step) = ready.pop() {
+        for released in released_by.get(step).into_iter().flatten() {
+            if let Some(count) = waiting_on.get_mut(released) {
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(released);
+                }
+            }
+        }
+    }
+
+    waiting_on
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(step, _)| step.to_string())
+        .collect()
+}
+
 #[test]
 fn first_available_steps_from_example() {
     let input = "Step C must be finished before step A can begin.
@@ -126,7 +264,7 @@ Step A must be finished before step D can begin.
 Step B must be finished before step E can begin.
 Step D must be finished before step E can begin.
 Step F must be finished before step E can begin.";
-    let seq = Sequencer::new_from_input(input);
+    let seq = Sequencer::new_from_input(input).unwrap();
     let expected_result: Vec<String> = vec![String::from("C")];
     assert_eq!(expected_result, seq.available_steps());
 }
@@ -140,6 +278,18 @@ Step A must be finished before step D can begin.
 Step B must be finished before step E can begin.
 Step D must be finished before step E can begin.
 Step F must be finished before step E can begin.";
-    let mut seq = Sequencer::new_from_input(input);
+    let mut seq = Sequencer::new_from_input(input).unwrap();
     assert_eq!("CABDFE", seq.sequence());
 }
+
+#[test]
+fn test_sequencer_rejects_a_loop() {
+    let input = "Step C must be finished before step A can begin.
+Step A must be finished before step B can begin.
+Step B must be finished before step A can begin.";
+    let err = Sequencer::new_from_input(input).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "steps A, B can never begin as they depend on each other in a loop"
+    );
+}
@@ -0,0 +1,6 @@
+use common::bench::bench_solution;
+use day1::ReportRepair;
+
+fn main() {
+    bench_solution::<ReportRepair>("day1");
+}
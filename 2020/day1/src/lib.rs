@@ -0,0 +1,115 @@
+use common::generate::{Generate, Rng};
+use common::input::Input;
+use common::parse::{parse_lines, ParseError};
+use common::solution::{Answer, Solution};
+
+// Problem statement:
+// Find the two numbers from the input which added together result in 2020
+
+/// Day 1: Report Repair.
+pub struct ReportRepair;
+
+impl Solution for ReportRepair {
+    type Parsed<'a> = Vec<i64>;
+
+    /// Read the expenses from the input, largest first.
+    fn parse(input: &Input) -> Result<Vec<i64>, ParseError> {
+        let mut expenses: Vec<i64> = parse_lines(input.as_str(), |line| {
+            line.parse::<i64>()
+                .map_err(|err| ParseError::new(line, format!("bad expense, {}", err)))
+        })?;
+
+        expenses.sort();
+        expenses.reverse();
+        Ok(expenses)
+    }
+
+    /// Product of the two expenses that add up to 2020.
+    fn part1(expenses: &Vec<i64>) -> Answer {
+        for expense in expenses.iter() {
+            if remainder(*expense) > 0 {
+                let required = remainder(*expense);
+                for potential in expenses.iter() {
+                    if *potential == required {
+                        return (expense * required).into();
+                    }
+                }
+            }
+        }
+        Answer::Unsolved
+    }
+
+    /// Product of the three expenses that add up to 2020.
+    fn part2(expenses: &Vec<i64>) -> Answer {
+        for expense in expenses.iter() {
+            if remainder(*expense) > 0 {
+                for potential in expenses.iter() {
+                    if remainder(expense + potential) > 0 {
+                        for final_part in expenses.iter() {
+                            if *final_part == remainder(expense + potential) {
+                                return (expense * potential * final_part).into();
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Answer::Unsolved
+    }
+}
+
+impl Generate for ReportRepair {
+    const REAL_SIZE: usize = 200;
+
+    /// Expenses too large for any two or three of them to add up to 2020, apart from a pair and a
+    /// triple that are mixed in so both parts have an answer.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let pair = rng.between(10, 1009);
+        let (first, second) = (rng.between(10, 500), rng.between(10, 500));
+        let mut expenses = vec![
+            pair,
+            remainder(pair),
+            first,
+            second,
+            remainder(first + second),
+        ];
+        while expenses.len() < size {
+            expenses.push(rng.between(1011, 2010));
+        }
+        rng.shuffle(&mut expenses);
+        expenses.truncate(size);
+
+        expenses
+            .iter()
+            .map(|expense| format!("{}\n", expense))
+            .collect()
+    }
+}
+
+fn remainder(origin: i64) -> i64 {
+    2020 - origin
+}
+
+#[test]
+fn test_remainder() {
+    assert_eq!(remainder(20), 2000);
+    assert_eq!(remainder(4040), -2020);
+}
+
+#[test]
+fn test_parse_rejects_bad_expenses() {
+    let err = ReportRepair::parse(&Input::new("1721\n979\n3 66\n")).unwrap_err();
+    assert_eq!(err.line, 3);
+    assert_eq!(err.text, "3 66");
+}
+
+#[test]
+fn test_generated_input_has_answers() {
+    use common::generate::{generate, Size};
+
+    let input = Input::new(&generate::<ReportRepair>(Size::Records(100), 8));
+    let expenses = ReportRepair::parse(&input).unwrap();
+    assert_eq!(expenses.len(), 100);
+    assert_ne!(ReportRepair::part1(&expenses), Answer::Unsolved);
+    assert_ne!(ReportRepair::part2(&expenses), Answer::Unsolved);
+}
@@ -0,0 +1,6 @@
+use common::bench::bench_solution;
+use day2::PasswordPhilosophy;
+
+fn main() {
+    bench_solution::<PasswordPhilosophy>("day2");
+}
@@ -0,0 +1,213 @@
+use common::generate::{Generate, Rng};
+use common::input::Input;
+use common::parse::{parse_lines, ParseError};
+use common::solution::{Answer, Solution};
+
+/// Day 2: Password Philosophy.
+pub struct PasswordPhilosophy;
+
+impl Solution for PasswordPhilosophy {
+    /// Each line read as both kinds of policy along with the password they apply to.
+    type Parsed<'a> = Vec<PasswordEntry<'a>>;
+
+    fn parse(input: &Input) -> Result<Vec<PasswordEntry<'_>>, ParseError> {
+        parse_lines(input.as_str(), PasswordEntry::from_input_line)
+    }
+
+    /// Number of passwords with an occurrence count inside the policy range.
+    fn part1(entries: &Vec<PasswordEntry<'_>>) -> Answer {
+        let mut part1_count: i64 = 0;
+        for entry in entries {
+            if entry.part1.matches(entry.password) {
+                part1_count += 1;
+            }
+        }
+        part1_count.into()
+    }
+
+    /// Number of passwords with the character in exactly one of the policy positions.
+    fn part2(entries: &Vec<PasswordEntry<'_>>) -> Answer {
+        let mut part2_count: i64 = 0;
+        for entry in entries {
+            if entry.part2.matches(entry.password) {
+                part2_count += 1;
+            }
+        }
+        part2_count.into()
+    }
+}
+
+impl Generate for PasswordPhilosophy {
+    const REAL_SIZE: usize = 1000;
+
+    /// Policies with a range of up to 20 for a lowercase letter, and passwords long enough to
+    /// reach the end of the range that lean towards using the letter.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let letters: Vec<char> = ('a'..='z').collect();
+        let mut input = String::new();
+        for _ in 0..size {
+            let first = rng.between(1, 10);
+            let second = first + rng.between(1, 10);
+            let character = *rng.pick(&letters);
+            let password: String = (0..rng.between(second, 20))
+                .map(|_| {
+                    if rng.one_in(3) {
+                        character
+                    } else {
+                        *rng.pick(&letters)
+                    }
+                })
+                .collect();
+            input.push_str(&format!(
+                "{}-{} {}: {}\n",
+                first, second, character, password
+            ));
+        }
+        input
+    }
+}
+
+#[derive(Debug)]
+/// A line of the password database.
+///
+/// The line looks like: -
+///
+/// 1-3 a: abcde
+pub struct PasswordEntry<'a> {
+    part1: Part1PasswordPolicy<'a>,
+    part2: Part2PasswordPolicy<'a>,
+    password: &'a str,
+}
+
+impl<'a> PasswordEntry<'a> {
+    pub fn from_input_line(line: &'a str) -> Result<PasswordEntry<'a>, ParseError> {
+        let line_parts: Vec<&str> = line.split(' ').collect();
+        if line_parts.len() != 3 {
+            return Err(ParseError::new(line, "expected an entry like 1-3 a: abcde"));
+        }
+        let err = |reason: String| ParseError::new(line, reason);
+
+        Ok(PasswordEntry {
+            part1: Part1PasswordPolicy::new(line_parts[0], line_parts[1]).map_err(err)?,
+            part2: Part2PasswordPolicy::new(line_parts[0], line_parts[1]).map_err(err)?,
+            password: line_parts[2],
+        })
+    }
+}
+
+/// Split the policy into its two numbers and the character it is about. The reason is returned
+/// when it isn't in the form `1-3 a:`.
+fn read_policy<'a>(policy: &str, char: &'a str) -> Result<(usize, usize, &'a str), String> {
+    let character = match char.strip_suffix(':') {
+        Some(character) if character.chars().count() == 1 => character,
+        _ => {
+            return Err(format!(
+                "expected a single character then ':', got {:?}",
+                char
+            ))
+        }
+    };
+    let (first, second) = match policy.split_once('-') {
+        Some(range) => range,
+        None => return Err(format!("expected a range like 1-3, got {:?}", policy)),
+    };
+    let number = |n: &str| {
+        n.parse::<usize>()
+            .map_err(|err| format!("{:?} in the range: {}", n, err))
+    };
+    Ok((number(first)?, number(second)?, character))
+}
+
+#[derive(Debug)]
+pub struct Part1PasswordPolicy<'a> {
+    min: usize,
+    max: usize,
+    character: &'a str,
+}
+
+impl<'a> Part1PasswordPolicy<'a> {
+    pub fn new(policy: &'a str, char: &'a str) -> Result<Part1PasswordPolicy<'a>, String> {
+        let (min, max, character) = read_policy(policy, char)?;
+
+        Ok(Part1PasswordPolicy {
+            min,
+            max,
+            character,
+        })
+    }
+
+    pub fn matches(&self, password: &str) -> bool {
+        let matches: Vec<_> = password.match_indices(self.character).collect();
+
+        matches.len() >= self.min && matches.len() <= self.max
+    }
+}
+
+#[derive(Debug)]
+pub struct Part2PasswordPolicy<'a> {
+    first_position: usize,
+    second_position: usize,
+    character: &'a str,
+}
+
+impl<'a> Part2PasswordPolicy<'a> {
+    pub fn new(policy: &'a str, char: &'a str) -> Result<Part2PasswordPolicy<'a>, String> {
+        let (first_position, second_position, character) = read_policy(policy, char)?;
+        if first_position == 0 || second_position < first_position {
+            return Err(format!(
+                "positions count from 1 and go up, got {:?}",
+                policy
+            ));
+        }
+
+        Ok(Part2PasswordPolicy {
+            first_position,
+            second_position,
+            character,
+        })
+    }
+
+    pub fn matches(&self, password: &str) -> bool {
+        let chars: Vec<char> = password.chars().collect();
+        let wanted = self.character.chars().next();
+        if chars.len() >= self.second_position {
+            let in_first = Some(chars[self.first_position - 1]) == wanted;
+            let in_second = Some(chars[self.second_position - 1]) == wanted;
+            return in_first != in_second;
+        }
+        false
+    }
+}
+
+#[test]
+fn test_password_entries() {
+    let input = Input::new("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n");
+    let entries = PasswordPhilosophy::parse(&input).unwrap();
+    assert_eq!(PasswordPhilosophy::part1(&entries), Answer::Number(2));
+    assert_eq!(PasswordPhilosophy::part2(&entries), Answer::Number(1));
+}
+
+#[test]
+fn test_password_entry_from_malformed_line() {
+    let err = PasswordEntry::from_input_line("1-3 a abcde").unwrap_err();
+    assert_eq!(
+        err.reason,
+        "expected a single character then ':', got \"a\""
+    );
+
+    let err = PasswordEntry::from_input_line("0-3 a: abcde").unwrap_err();
+    assert_eq!(err.reason, "positions count from 1 and go up, got \"0-3\"");
+
+    let err = PasswordEntry::from_input_line("1-3 a:abcde").unwrap_err();
+    assert_eq!(err.reason, "expected an entry like 1-3 a: abcde");
+}
+
+#[test]
+fn test_generated_input_parses() {
+    use common::generate::{generate, Size};
+
+    let input = Input::new(&generate::<PasswordPhilosophy>(Size::Records(100), 9));
+    let entries = PasswordPhilosophy::parse(&input).unwrap();
+    assert_eq!(entries.len(), 100);
+    assert_ne!(PasswordPhilosophy::part2(&entries), Answer::Number(0));
+}
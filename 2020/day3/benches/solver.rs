@@ -0,0 +1,6 @@
+use common::bench::bench_solution;
+use day3::TobogganTrajectory;
+
+fn main() {
+    bench_solution::<TobogganTrajectory>("day3");
+}
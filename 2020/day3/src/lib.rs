@@ -0,0 +1,192 @@
+use common::generate::{Generate, Rng};
+use common::input::Input;
+use common::parse::ParseError;
+use common::solution::{Answer, Solution};
+
+/// Day 3: Toboggan Trajectory.
+pub struct TobogganTrajectory;
+
+impl Solution for TobogganTrajectory {
+    type Parsed<'a> = toboggan_corp::TobogganMap;
+
+    /// Each row of the map is made up of open squares `.` and trees `#`, every row as wide as the
+    /// first.
+    fn parse(input: &Input) -> Result<toboggan_corp::TobogganMap, ParseError> {
+        let cells = input.grid();
+        let width = cells.first().map_or(0, |row| row.len());
+        for (index, row) in cells.iter().enumerate() {
+            let reason = if row.is_empty() {
+                String::from("empty row")
+            } else if row.len() != width {
+                format!("{} squares wide, expected {}", row.len(), width)
+            } else {
+                match row.iter().find(|c| **c != '.' && **c != '#') {
+                    Some(c) => format!("{:?} isn't a square", c),
+                    None => continue,
+                }
+            };
+            let line: String = row.iter().collect();
+            return Err(ParseError::new(&line, reason).on_line(index + 1));
+        }
+        Ok(toboggan_corp::TobogganMap::from_cells(cells))
+    }
+
+    /// Trees hit sliding right 3, down 1.
+    fn part1(toboggan_map: &toboggan_corp::TobogganMap) -> Answer {
+        let counts = tree_counts(toboggan_map, vec![toboggan_corp::Toboggan::new((3, 1))]);
+        counts[0].into()
+    }
+
+    /// Product of the trees hit on each of the listed slopes.
+    fn part2(toboggan_map: &toboggan_corp::TobogganMap) -> Answer {
+        let toboggans: Vec<toboggan_corp::Toboggan> = vec![
+            toboggan_corp::Toboggan::new((1, 1)),
+            toboggan_corp::Toboggan::new((3, 1)),
+            toboggan_corp::Toboggan::new((5, 1)),
+            toboggan_corp::Toboggan::new((7, 1)),
+            toboggan_corp::Toboggan::new((1, 2)),
+        ];
+
+        let mut result: usize = 1;
+        for count in &tree_counts(toboggan_map, toboggans) {
+            result *= count;
+        }
+        result.into()
+    }
+}
+
+impl Generate for TobogganTrajectory {
+    /// The number of rows in the map.
+    const REAL_SIZE: usize = 323;
+
+    /// Rows 31 squares wide, the same as the real map, with about one square in five a tree.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            for _ in 0..31 {
+                input.push(if rng.one_in(5) { '#' } else { '.' });
+            }
+            input.push('\n');
+        }
+        input
+    }
+}
+
+/// Number of trees hit by each toboggan as it slides down the map.
+fn tree_counts(
+    toboggan_map: &toboggan_corp::TobogganMap,
+    toboggans: Vec<toboggan_corp::Toboggan>,
+) -> Vec<usize> {
+    let mut tree_counts: Vec<usize> = vec![];
+    for mut toboggan in toboggans {
+        let mut tree_count: usize = 0;
+        let mut check_position = toboggan.next();
+        while toboggan_map.still_on_map(check_position) {
+            if toboggan_map.tree_at_location(check_position) {
+                tree_count += 1;
+            }
+            check_position = toboggan.next()
+        }
+        tree_counts.push(tree_count);
+    }
+    tree_counts
+}
+
+pub mod toboggan_corp {
+    #[derive(Debug)]
+    pub struct TobogganMap {
+        cells: Vec<Vec<char>>,
+    }
+
+    impl TobogganMap {
+        /// Build the map from rows of cells that have already been split into characters.
+        pub fn from_cells(cells: Vec<Vec<char>>) -> TobogganMap {
+            TobogganMap { cells }
+        }
+
+        pub fn tree_at_location(&self, location: (usize, usize)) -> bool {
+            self.cells[location.1][location.0 % self.cells[location.1].len()] == '#'
+        }
+
+        pub fn still_on_map(&self, location: (usize, usize)) -> bool {
+            location.1 < self.cells.len()
+        }
+    }
+
+    #[test]
+    fn test_create_toboggan_map() {
+        let tm: TobogganMap = TobogganMap::from_cells(vec![
+            "..##.......".chars().collect(),
+            "#...#...#..".chars().collect(),
+        ]);
+        assert_eq!(2, tm.cells.len());
+        for row in tm.cells {
+            assert_eq!(11, row.len());
+        }
+    }
+
+    #[test]
+    fn test_tree_at_location() {
+        let tm: TobogganMap = TobogganMap::from_cells(vec![
+            "..##.......".chars().collect(),
+            "#...#...#..".chars().collect(),
+        ]);
+        assert!(!tm.tree_at_location((0, 0)));
+        assert!(tm.tree_at_location((11, 1)));
+        assert!(tm.tree_at_location((15, 1)));
+        assert!(!tm.tree_at_location((16, 1)));
+    }
+
+    pub struct Toboggan {
+        current_position: (usize, usize),
+        moves: (usize, usize),
+    }
+
+    impl Toboggan {
+        pub fn new(moves: (usize, usize)) -> Toboggan {
+            Toboggan {
+                current_position: (0, 0),
+                moves,
+            }
+        }
+
+        // calculate the next coordinates that the toboggan will slide to.
+        #[allow(clippy::should_implement_trait)]
+        pub fn next(&mut self) -> (usize, usize) {
+            self.current_position = (
+                self.current_position.0 + self.moves.0,
+                self.current_position.1 + self.moves.1,
+            );
+            self.current_position
+        }
+    }
+
+    #[test]
+    fn test_toboggan_next() {
+        let mut t = Toboggan::new((3, 1));
+        assert_eq!(t.next(), (3, 1));
+        assert_eq!(t.next(), (6, 2));
+    }
+}
+
+#[test]
+fn test_parse_rejects_unknown_squares() {
+    let err = TobogganTrajectory::parse(&Input::new("..##...\n#..O..#\n")).unwrap_err();
+    assert_eq!(err.line, 2);
+    assert_eq!(err.reason, "'O' isn't a square");
+
+    let err = TobogganTrajectory::parse(&Input::new("..##...\n#..#..#\n#..#\n")).unwrap_err();
+    assert_eq!(err.line, 3);
+    assert_eq!(err.text, "#..#");
+    assert_eq!(err.reason, "4 squares wide, expected 7");
+}
+
+#[test]
+fn test_generated_input_parses() {
+    use common::generate::{generate, Size};
+
+    let input = Input::new(&generate::<TobogganTrajectory>(Size::Records(100), 10));
+    let toboggan_map = TobogganTrajectory::parse(&input).unwrap();
+    assert!(toboggan_map.still_on_map((0, 99)));
+    assert!(!toboggan_map.still_on_map((0, 100)));
+}
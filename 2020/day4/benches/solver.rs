@@ -0,0 +1,6 @@
+use common::bench::bench_solution;
+use day4::PassportProcessing;
+
+fn main() {
+    bench_solution::<PassportProcessing>("day4");
+}
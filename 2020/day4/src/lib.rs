@@ -0,0 +1,333 @@
+use crate::identity_documents::Passport;
+use common::generate::{Generate, Rng};
+use common::input::Input;
+use common::parse::ParseError;
+use common::solution::{Answer, Solution};
+
+/// Day 4: Passport Processing.
+pub struct PassportProcessing;
+
+impl Solution for PassportProcessing {
+    type Parsed<'a> = Vec<Passport>;
+
+    /// Passports with missing or invalid fields are expected, so nothing here is rejected.
+    fn parse(input: &Input) -> Result<Vec<Passport>, ParseError> {
+        let mut passports: Vec<Passport> = vec![];
+        for input_field in parse_input(input) {
+            if let Some(passport) = Passport::new(input_field) {
+                passports.push(passport);
+            }
+        }
+        Ok(passports)
+    }
+
+    /// Number of passports with every required field present.
+    fn part1(passports: &Vec<Passport>) -> Answer {
+        let mut part1_valid_count = 0;
+        for passport in passports {
+            if passport.part1_valid() {
+                part1_valid_count += 1;
+            }
+        }
+        Answer::from(part1_valid_count)
+    }
+
+    /// Number of passports with every required field present and holding a valid value.
+    fn part2(passports: &Vec<Passport>) -> Answer {
+        let mut part2_valid_count = 0;
+        for passport in passports {
+            if passport.part2_valid() {
+                part2_valid_count += 1;
+            }
+        }
+        Answer::from(part2_valid_count)
+    }
+}
+
+impl Generate for PassportProcessing {
+    /// The number of passports in the batch.
+    const REAL_SIZE: usize = 285;
+
+    /// Passports with their fields in any order and spread over a few lines. Most fields are
+    /// present and most values valid, leaving a mix of passports for both parts to turn away.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut passports: Vec<String> = vec![];
+        for _ in 0..size {
+            let mut fields: Vec<String> = vec![];
+            for key in &["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"] {
+                let present = if *key == "cid" {
+                    rng.one_in(2)
+                } else {
+                    !rng.one_in(8)
+                };
+                if present {
+                    fields.push(format!("{}:{}", key, passport_value(key, rng)));
+                }
+            }
+            rng.shuffle(&mut fields);
+
+            let mut passport = String::new();
+            for (index, field) in fields.iter().enumerate() {
+                if index > 0 {
+                    passport.push(if rng.one_in(3) { '\n' } else { ' ' });
+                }
+                passport.push_str(field);
+            }
+            passports.push(passport);
+        }
+        passports.join("\n\n") + "\n"
+    }
+}
+
+/// A value for the field, valid most of the time.
+fn passport_value(key: &str, rng: &mut Rng) -> String {
+    let valid = !rng.one_in(4);
+    match key {
+        "byr" if valid => rng.between(1920, 2002).to_string(),
+        "iyr" if valid => rng.between(2010, 2020).to_string(),
+        "eyr" if valid => rng.between(2020, 2030).to_string(),
+        "byr" | "iyr" | "eyr" => rng.between(1900, 2040).to_string(),
+        "hgt" if valid && rng.one_in(2) => format!("{}in", rng.between(59, 76)),
+        "hgt" if valid => format!("{}cm", rng.between(150, 193)),
+        "hgt" => format!("{}{}", rng.between(40, 200), rng.pick(&["cm", "in", ""])),
+        "hcl" if valid => format!("#{:06x}", rng.below(0x100_0000)),
+        "hcl" => format!("{:06x}", rng.below(0x100_0000)),
+        "ecl" if valid => rng
+            .pick(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"])
+            .to_string(),
+        "ecl" => rng.pick(&["red", "xry", "#b6652a"]).to_string(),
+        "pid" if valid => format!("{:09}", rng.below(1_000_000_000)),
+        "pid" => rng.below(1_000_000_000_000).to_string(),
+        _ => rng.between(100, 350).to_string(),
+    }
+}
+
+/// Split each blank line separated passport record into its individual `key:value` fields.
+fn parse_input(input: &Input) -> Vec<Vec<String>> {
+    let mut password_entries: Vec<Vec<String>> = vec![];
+    for record in input.records() {
+        let mut password_entry: Vec<String> = vec![];
+        for line in record {
+            for line_part in line.split(' ') {
+                password_entry.push(String::from(line_part));
+            }
+        }
+        password_entries.push(password_entry);
+    }
+    password_entries
+}
+
+#[test]
+fn test_parse_input() {
+    let input = Input::from_path("test_input").unwrap();
+    assert_eq!(parse_input(&input).len(), 4);
+    let mut valid_passports: Vec<Passport> = vec![];
+    for input_field in parse_input(&input) {
+        if let Some(passport) = Passport::new(input_field) {
+            if passport.part1_valid() {
+                valid_passports.push(passport.clone());
+            }
+        }
+    }
+    assert_eq!(valid_passports.len(), 2);
+}
+
+#[test]
+fn test_generated_input_parses() {
+    use common::generate::{generate, Size};
+
+    let input = Input::new(&generate::<PassportProcessing>(Size::Records(100), 11));
+    let passports = PassportProcessing::parse(&input).unwrap();
+    assert_eq!(passports.len(), 100);
+    assert_ne!(PassportProcessing::part2(&passports), Answer::Number(0));
+}
+
+pub mod identity_documents {
+    use regex::Regex;
+
+    #[derive(Debug, Clone)]
+    pub struct Passport {
+        birth_year: i16,
+        issue_year: i16,
+        expiration_year: i16,
+        height: String,
+        hair_colour: String,
+        eye_colour: String,
+        id: String,
+        country_id: String,
+    }
+
+    impl Passport {
+        pub fn new(fields: Vec<String>) -> Option<Passport> {
+            let mut passport = Passport {
+                birth_year: -1,
+                issue_year: -1,
+                expiration_year: -1,
+                height: "".to_string(),
+                hair_colour: "".to_string(),
+                eye_colour: "".to_string(),
+                id: "".to_string(),
+                country_id: "".to_string(),
+            };
+            for field in fields {
+                let parts: Vec<&str> = field.split(':').collect();
+                if parts.len() != 2 {
+                    continue;
+                }
+                match parts[0] {
+                    "byr" => {
+                        if let Ok(year) = parts[1].parse::<i16>() {
+                            passport.birth_year = year;
+                        }
+                    }
+                    "iyr" => {
+                        if let Ok(year) = parts[1].parse::<i16>() {
+                            passport.issue_year = year;
+                        }
+                    }
+                    "eyr" => {
+                        if let Ok(year) = parts[1].parse::<i16>() {
+                            passport.expiration_year = year;
+                        }
+                    }
+                    "hgt" => {
+                        passport.height = String::from(parts[1]);
+                    }
+                    "hcl" => {
+                        passport.hair_colour = String::from(parts[1]);
+                    }
+                    "ecl" => {
+                        passport.eye_colour = String::from(parts[1]);
+                    }
+                    "pid" => passport.id = String::from(parts[1]),
+                    "cid" => {
+                        passport.country_id = String::from(parts[1]);
+                    }
+                    // keys the puzzle doesn't know about are ignored.
+                    &_ => {}
+                }
+            }
+            Some(passport)
+        }
+
+        pub fn part1_valid(&self) -> bool {
+            if self.height.is_empty() {
+                return false;
+            }
+            if self.issue_year < 0 {
+                return false;
+            }
+            if self.birth_year < 0 {
+                return false;
+            }
+            if self.hair_colour.is_empty() {
+                return false;
+            }
+            if self.expiration_year < 0 {
+                return false;
+            }
+            if self.eye_colour.is_empty() {
+                return false;
+            }
+            if self.id.is_empty() {
+                return false;
+            }
+            true
+        }
+
+        pub fn part2_valid(&self) -> bool {
+            if self.birth_year < 1920 || self.birth_year > 2002 {
+                return false;
+            }
+            if self.issue_year < 2010 || self.issue_year > 2020 {
+                return false;
+            }
+
+            if self.expiration_year < 2020 || self.expiration_year > 2030 {
+                return false;
+            }
+
+            // a height that isn't a number of centimetres or inches makes the passport invalid.
+            let height_range = if self.height.ends_with("cm") {
+                150..=193
+            } else if self.height.ends_with("in") {
+                59..=76
+            } else {
+                return false;
+            };
+            let height_number = self.height[..self.height.len() - 2].parse::<i16>().ok();
+            if !height_number.is_some_and(|height| height_range.contains(&height)) {
+                return false;
+            }
+            let hair_colour_re = Regex::new(r"(^#[0-9a-f]{6})$").unwrap();
+            if !hair_colour_re.is_match(&self.hair_colour) {
+                return false;
+            }
+            let eye_colours: Vec<&str> = vec!["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+            let mut eye_colour_correct = false;
+            for eye_colour in eye_colours {
+                if self.eye_colour == eye_colour {
+                    eye_colour_correct = true;
+                }
+            }
+            if !eye_colour_correct {
+                return false;
+            }
+
+            let passport_id_re = Regex::new(r"(^[0-9]{9})$").unwrap();
+            if !passport_id_re.is_match(&self.id) {
+                return false;
+            }
+
+            true
+        }
+    }
+
+    #[test]
+    fn test_new_passport() {
+        let fields: Vec<String> = vec![
+            String::from("ecl:gry"),
+            String::from("pid:860033327"),
+            String::from("eyr:2020"),
+            String::from("hcl:#fffffd"),
+            String::from("byr:1937"),
+            String::from("iyr:2017"),
+            String::from("cid:147"),
+            String::from("hgt:183cm"),
+        ];
+        if let Some(passport) = Passport::new(fields) {
+            assert_eq!(passport.country_id, String::from("147"));
+            assert_eq!(passport.id, String::from("860033327"));
+            assert_eq!(passport.eye_colour, String::from("gry"));
+            assert_eq!(passport.expiration_year, 2020);
+            assert_eq!(passport.hair_colour, String::from("#fffffd"));
+            assert_eq!(passport.birth_year, 1937);
+            assert_eq!(passport.issue_year, 2017);
+            assert_eq!(passport.height, String::from("183cm"));
+        }
+    }
+
+    #[test]
+    fn test_malformed_height() {
+        for (height, valid) in &[
+            ("18xcm", false),
+            ("cm", false),
+            ("6o in", false),
+            ("183cm", true),
+            ("70in", true),
+        ] {
+            let fields: Vec<String> = vec![
+                String::from("ecl:gry"),
+                String::from("pid:860033327"),
+                String::from("eyr:2020"),
+                String::from("hcl:#fffffd"),
+                String::from("byr:1937"),
+                String::from("iyr:2017"),
+                format!("hgt:{}", height),
+                String::from("xyz:unknown"),
+            ];
+            let passport = Passport::new(fields).unwrap();
+            assert_eq!(passport.part2_valid(), *valid, "{}", height);
+        }
+    }
+}
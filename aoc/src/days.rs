@@ -0,0 +1,207 @@
+use common::answers::ANSWERS_FILE_NAME;
+use common::generate::{generate, Size};
+use common::input::{Input, InputError, DEFAULT_FILE_NAME};
+use common::parse::ParseError;
+use common::solution::{solve, time, Solved, Timings};
+use std::error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Parses the input for a day's puzzle and answers the requested parts, timing each stage.
+pub type Solver = fn(&Input, &[u8]) -> Result<Solved, ParseError>;
+
+/// Times parsing the input for a day's puzzle and each requested part, over a number of runs.
+pub type Timer = fn(&Input, &[u8], u32) -> Result<Timings, ParseError>;
+
+/// Builds a synthetic input of the given size for a day's puzzle from a seed.
+pub type Generator = fn(Size, u64) -> String;
+
+/// A single day's puzzle and where to find its input.
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    /// Folder within the year holding the crate for the day.
+    pub dir: &'static str,
+    pub solve: Solver,
+    pub time: Timer,
+    pub generate: Generator,
+}
+
+impl Day {
+    /// Location of the input committed alongside the crate for the day.
+    pub fn input_path(&self) -> PathBuf {
+        self.crate_dir().join(DEFAULT_FILE_NAME)
+    }
+
+    /// Location of the answers recorded for the day's input.
+    pub fn answers_path(&self) -> PathBuf {
+        self.crate_dir().join(ANSWERS_FILE_NAME)
+    }
+
+    fn crate_dir(&self) -> PathBuf {
+        workspace_root().join(self.year.to_string()).join(self.dir)
+    }
+}
+
+#[derive(Debug)]
+/// Failure to run a day's puzzle, either its input couldn't be read or it couldn't be parsed.
+pub enum PuzzleError {
+    Input(InputError),
+    Parse(ParseError),
+}
+
+impl From<InputError> for PuzzleError {
+    fn from(err: InputError) -> PuzzleError {
+        PuzzleError::Input(err)
+    }
+}
+
+impl From<ParseError> for PuzzleError {
+    fn from(err: ParseError) -> PuzzleError {
+        PuzzleError::Parse(err)
+    }
+}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PuzzleError::Input(err) => write!(f, "{}", err),
+            PuzzleError::Parse(err) => write!(f, "couldn't parse the input, {}", err),
+        }
+    }
+}
+
+impl error::Error for PuzzleError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            PuzzleError::Input(err) => Some(err),
+            PuzzleError::Parse(err) => Some(err),
+        }
+    }
+}
+
+/// Every day solved so far, in the order they are run.
+pub const DAYS: &[Day] = &[
+    Day {
+        year: 2018,
+        day: 1,
+        dir: "d1",
+        solve: solve::<d1::ChronalCalibration>,
+        time: time::<d1::ChronalCalibration>,
+        generate: generate::<d1::ChronalCalibration>,
+    },
+    Day {
+        year: 2018,
+        day: 2,
+        dir: "d2",
+        solve: solve::<d2::InventoryManagement>,
+        time: time::<d2::InventoryManagement>,
+        generate: generate::<d2::InventoryManagement>,
+    },
+    Day {
+        year: 2018,
+        day: 3,
+        dir: "d3",
+        solve: solve::<d3::NoMatterHowYouSliceIt>,
+        time: time::<d3::NoMatterHowYouSliceIt>,
+        generate: generate::<d3::NoMatterHowYouSliceIt>,
+    },
+    Day {
+        year: 2018,
+        day: 4,
+        dir: "d4",
+        solve: solve::<d4::ReposeRecord>,
+        time: time::<d4::ReposeRecord>,
+        generate: generate::<d4::ReposeRecord>,
+    },
+    Day {
+        year: 2018,
+        day: 5,
+        dir: "d5",
+        solve: solve::<d5::AlchemicalReduction>,
+        time: time::<d5::AlchemicalReduction>,
+        generate: generate::<d5::AlchemicalReduction>,
+    },
+    Day {
+        year: 2018,
+        day: 6,
+        dir: "d6",
+        solve: solve::<d6::ChronalCoordinates>,
+        time: time::<d6::ChronalCoordinates>,
+        generate: generate::<d6::ChronalCoordinates>,
+    },
+    Day {
+        year: 2018,
+        day: 7,
+        dir: "d7",
+        solve: solve::<d7::SumOfItsParts>,
+        time: time::<d7::SumOfItsParts>,
+        generate: generate::<d7::SumOfItsParts>,
+    },
+    Day {
+        year: 2020,
+        day: 1,
+        dir: "day1",
+        solve: solve::<day1::ReportRepair>,
+        time: time::<day1::ReportRepair>,
+        generate: generate::<day1::ReportRepair>,
+    },
+    Day {
+        year: 2020,
+        day: 2,
+        dir: "day2",
+        solve: solve::<day2::PasswordPhilosophy>,
+        time: time::<day2::PasswordPhilosophy>,
+        generate: generate::<day2::PasswordPhilosophy>,
+    },
+    Day {
+        year: 2020,
+        day: 3,
+        dir: "day3",
+        solve: solve::<day3::TobogganTrajectory>,
+        time: time::<day3::TobogganTrajectory>,
+        generate: generate::<day3::TobogganTrajectory>,
+    },
+    Day {
+        year: 2020,
+        day: 4,
+        dir: "day4",
+        solve: solve::<day4::PassportProcessing>,
+        time: time::<day4::PassportProcessing>,
+        generate: generate::<day4::PassportProcessing>,
+    },
+];
+
+/// The days matching the requested year and day. Leaving either out matches all of them.
+pub fn select(year: Option<u16>, day: Option<u8>) -> Vec<&'static Day> {
+    DAYS.iter()
+        .filter(|d| year.is_none_or(|y| d.year == y))
+        .filter(|d| day.is_none_or(|n| d.day == n))
+        .collect()
+}
+
+/// The root of the workspace the runner was built in, each year's folder lives here.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+#[test]
+fn test_select_everything() {
+    assert_eq!(select(None, None).len(), DAYS.len());
+}
+
+#[test]
+fn test_select_year() {
+    let days = select(Some(2020), None);
+    assert!(days.len() >= 4);
+    assert!(days.iter().all(|d| d.year == 2020));
+}
+
+#[test]
+fn test_select_day() {
+    let days = select(Some(2018), Some(4));
+    assert_eq!(days.len(), 1);
+    assert_eq!(days[0].dir, "d4");
+    assert!(days[0].input_path().ends_with("2018/d4/input"));
+    assert!(days[0].answers_path().ends_with("2018/d4/answers"));
+}
@@ -0,0 +1,224 @@
+use common::input::{Input, InputError};
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Site the puzzle inputs are downloaded from unless another is configured.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the session token used to download inputs.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Environment variable that overrides the site inputs are downloaded from.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// Downloads puzzle inputs and keeps a copy of each so that it is only ever downloaded once.
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+}
+
+impl Fetcher {
+    pub fn new(base_url: &str, session: &str) -> Fetcher {
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Configure the fetcher, taking anything not supplied from the environment. The session
+    /// token is required, the site falls back to the real one.
+    pub fn configure(session: Option<&str>, base_url: Option<&str>) -> Result<Fetcher, FetchError> {
+        let session = match session {
+            Some(session) => session.to_string(),
+            None => env::var(SESSION_VAR).map_err(|_| FetchError::MissingSession)?,
+        };
+        let base_url = match base_url {
+            Some(base_url) => base_url.to_string(),
+            None => env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string()),
+        };
+        Ok(Fetcher::new(&base_url, &session))
+    }
+
+    /// Where the input for the day is downloaded from.
+    pub fn url(&self, year: u16, day: u8) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, year, day)
+    }
+
+    /// Provide the input for the day. If it has already been cached at the provided path that
+    /// copy is used, otherwise it is downloaded and saved there first.
+    pub fn fetch(&self, year: u16, day: u8, cache: &Path) -> Result<Input, FetchError> {
+        if cache.exists() {
+            return Input::from_path(cache).map_err(FetchError::Cache);
+        }
+
+        let body = self.download(year, day)?;
+        if let Some(dir) = cache.parent() {
+            fs::create_dir_all(dir).map_err(FetchError::Io)?;
+        }
+        fs::write(cache, &body).map_err(FetchError::Io)?;
+
+        Ok(Input::new(&body))
+    }
+
+    fn download(&self, year: u16, day: u8) -> Result<String, FetchError> {
+        let url = self.url(year, day);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", "github.com/JonPulfer/advent_of_code")
+            .call();
+
+        match response {
+            Ok(response) => response.into_string().map_err(FetchError::Io),
+            Err(ureq::Error::Status(status, _)) => Err(FetchError::Status { url, status }),
+            Err(ureq::Error::Transport(transport)) => Err(FetchError::Transport {
+                url,
+                reason: transport.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Failure to provide an input, either from the cache or by downloading it.
+pub enum FetchError {
+    /// No session token was configured.
+    MissingSession,
+    /// The site responded but not with the input.
+    Status { url: String, status: u16 },
+    /// The site couldn't be reached.
+    Transport { url: String, reason: String },
+    /// The cached copy exists but couldn't be read.
+    Cache(InputError),
+    /// The downloaded input couldn't be read or saved.
+    Io(io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::MissingSession => {
+                write!(f, "no session token given, pass one or set {}", SESSION_VAR)
+            }
+            FetchError::Status { url, status } => {
+                write!(f, "couldn't download {}: status {}", url, status)
+            }
+            FetchError::Transport { url, reason } => {
+                write!(f, "couldn't download {}: {}", url, reason)
+            }
+            FetchError::Cache(err) => write!(f, "{}", err),
+            FetchError::Io(err) => write!(f, "couldn't save the input: {}", err),
+        }
+    }
+}
+
+impl error::Error for FetchError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            FetchError::Cache(err) => Some(err),
+            FetchError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+/// Serve the body for `/2018/day/1/input` to any request carrying the session cookie, and count
+/// the requests that are made. Returns the base URL of the server and the counter.
+fn stub_server(body: &'static str) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            counter.fetch_add(1, Ordering::SeqCst);
+
+            let mut request: Vec<String> = vec![];
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                request.push(line.trim().to_string());
+            }
+
+            let authorised = request.iter().any(|h| h == "Cookie: session=secret");
+            let response = if request[0] == "GET /2018/day/1/input HTTP/1.1" && authorised {
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            } else {
+                "HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string()
+            };
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    (base_url, requests)
+}
+
+#[cfg(test)]
+/// A cache location unique to the test so tests can run alongside each other.
+fn test_cache(name: &str) -> std::path::PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir.join("2018").join("d1").join("input")
+}
+
+#[test]
+fn test_fetch_downloads_and_caches() {
+    use std::sync::atomic::Ordering;
+
+    let (base_url, requests) = stub_server("+1\n-2\n+3\n");
+    let fetcher = Fetcher::new(&base_url, "secret");
+    let cache = test_cache("caches");
+
+    let input = fetcher.fetch(2018, 1, &cache).unwrap();
+    assert_eq!(input.as_str(), "+1\n-2\n+3");
+    assert_eq!(fs::read_to_string(&cache).unwrap(), "+1\n-2\n+3\n");
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    // The second fetch is served from the cache without another download.
+    let input = fetcher.fetch(2018, 1, &cache).unwrap();
+    assert_eq!(input.as_str(), "+1\n-2\n+3");
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_fetch_rejected() {
+    let (base_url, _requests) = stub_server("+1\n");
+    let fetcher = Fetcher::new(&base_url, "wrong");
+    let cache = test_cache("rejected");
+
+    match fetcher.fetch(2018, 1, &cache) {
+        Err(FetchError::Status { status, .. }) => assert_eq!(status, 400),
+        other => panic!("expected a status error, got {:?}", other),
+    }
+    assert!(!cache.exists());
+}
+
+#[test]
+fn test_url() {
+    let fetcher = Fetcher::new("https://adventofcode.com/", "secret");
+    assert_eq!(
+        fetcher.url(2020, 4),
+        "https://adventofcode.com/2020/day/4/input"
+    );
+}
@@ -0,0 +1,8 @@
+//! Runs, verifies and reports on the solvers for every day in the workspace.
+
+pub mod days;
+pub mod fetch;
+pub mod report;
+pub mod scaffold;
+pub mod timing;
+pub mod verify;
@@ -0,0 +1,457 @@
+extern crate clap;
+use aoc::days::{self, Day, PuzzleError};
+use aoc::fetch::Fetcher;
+use aoc::report::{Format, Record};
+use aoc::scaffold::Scaffold;
+use aoc::timing::{self, Baseline};
+use aoc::verify::check;
+use clap::{value_t, App, AppSettings, Arg, ArgMatches, SubCommand};
+use common::generate::Size;
+use common::input::{Input, InputError, STDIN_ARG};
+use common::solution::Answer;
+use std::io::{self, Write};
+use std::process;
+
+fn main() {
+    let matches = App::new("aoc")
+        .version("1.0")
+        .author("Jonathan Pulfer")
+        .about("Runs the Advent Of Code puzzle solvers")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            format_arg(puzzle_args(
+                SubCommand::with_name("run")
+                    .about("Run the puzzles for a day, a whole year or every year"),
+            ))
+            .arg(
+                Arg::with_name("input")
+                    .short("i")
+                    .long("input")
+                    .value_name("PATH")
+                    .help("Read the input from this file instead, or - for stdin")
+                    .requires("day")
+                    .takes_value(true),
+            ),
+        )
+        .subcommand(format_arg(puzzle_args(
+            SubCommand::with_name("verify")
+                .about("Check the puzzles still produce the answers recorded for their inputs"),
+        )))
+        .subcommand(
+            puzzle_args(
+                SubCommand::with_name("summary")
+                    .about("Time each stage of the puzzles and print them as a table"),
+            )
+            .arg(
+                Arg::with_name("runs")
+                    .short("r")
+                    .long("runs")
+                    .value_name("RUNS")
+                    .help("Run each stage this many times and report the fastest")
+                    .default_value("1")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("baseline")
+                    .short("b")
+                    .long("baseline")
+                    .value_name("PATH")
+                    .help("Compare the timings with a baseline saved earlier")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("save")
+                    .short("s")
+                    .long("save")
+                    .value_name("PATH")
+                    .help("Save the timings as a baseline to compare later runs with")
+                    .takes_value(true),
+            ),
+        )
+        .subcommand(
+            SubCommand::with_name("new")
+                .about("Create the crate for a new day and register it with the runner")
+                .arg(
+                    Arg::with_name("year")
+                        .value_name("YEAR")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("day")
+                        .value_name("DAY")
+                        .required(true)
+                        .index(2),
+                )
+                .arg(
+                    Arg::with_name("title")
+                        .short("t")
+                        .long("title")
+                        .value_name("TITLE")
+                        .help("Title of the puzzle, used to name the solution")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("fetch")
+                .about("Download the input for a day unless it has already been downloaded")
+                .arg(
+                    Arg::with_name("year")
+                        .value_name("YEAR")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("day")
+                        .value_name("DAY")
+                        .required(true)
+                        .index(2),
+                )
+                .arg(
+                    Arg::with_name("session")
+                        .long("session")
+                        .value_name("TOKEN")
+                        .help("Session token for the site. Defaults to the AOC_SESSION variable")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("base_url")
+                        .long("base-url")
+                        .value_name("URL")
+                        .help("Site to download from. Defaults to the AOC_BASE_URL variable or adventofcode.com")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("generate")
+                .about("Write a synthetic input for a day, to see how the puzzle copes at scale")
+                .arg(
+                    Arg::with_name("year")
+                        .value_name("YEAR")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("day")
+                        .value_name("DAY")
+                        .required(true)
+                        .index(2),
+                )
+                .arg(
+                    Arg::with_name("size")
+                        .short("n")
+                        .long("size")
+                        .value_name("RECORDS")
+                        .help("Number of records in the input, such as lines, claims or passports")
+                        .conflicts_with("scale")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("scale")
+                        .short("x")
+                        .long("scale")
+                        .value_name("TIMES")
+                        .help("Make the input this many times the size of the real one. Defaults to 1")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("seed")
+                        .long("seed")
+                        .value_name("SEED")
+                        .help("Seed for the input, the same seed always gives the same input")
+                        .default_value("1")
+                        .takes_value(true),
+                ),
+        )
+        .get_matches();
+
+    let succeeded = match matches.subcommand() {
+        ("run", Some(run_matches)) => run(run_matches),
+        ("verify", Some(verify_matches)) => verify_answers(verify_matches),
+        ("summary", Some(summary_matches)) => summary(summary_matches),
+        ("new", Some(new_matches)) => new_day(new_matches),
+        ("fetch", Some(fetch_matches)) => fetch(fetch_matches),
+        ("generate", Some(generate_matches)) => generate(generate_matches),
+        _ => true,
+    };
+    if !succeeded {
+        process::exit(1);
+    }
+}
+
+/// Add the arguments used to pick which puzzles and parts a subcommand works on.
+fn puzzle_args<'a, 'b>(cmd: App<'a, 'b>) -> App<'a, 'b> {
+    cmd.arg(
+        Arg::with_name("year")
+            .value_name("YEAR")
+            .help("Year of the puzzles. Every year is included when left out")
+            .index(1),
+    )
+    .arg(
+        Arg::with_name("day")
+            .value_name("DAY")
+            .help("Day of the puzzle. Every day in the year is included when left out")
+            .index(2),
+    )
+    .arg(
+        Arg::with_name("part")
+            .short("p")
+            .long("part")
+            .value_name("PART")
+            .help("Only include this part of the puzzle")
+            .possible_values(&["1", "2"])
+            .takes_value(true),
+    )
+}
+
+/// Add the argument choosing how results are written out.
+fn format_arg<'a, 'b>(cmd: App<'a, 'b>) -> App<'a, 'b> {
+    cmd.arg(
+        Arg::with_name("format")
+            .short("f")
+            .long("format")
+            .value_name("FORMAT")
+            .help("Write the results as text or as a JSON record per line")
+            .possible_values(Format::NAMES)
+            .default_value("text")
+            .takes_value(true),
+    )
+}
+
+/// The puzzles and parts picked by the arguments added with `puzzle_args`.
+fn selection(matches: &ArgMatches) -> (Vec<&'static Day>, Vec<u8>) {
+    let year = if matches.is_present("year") {
+        Some(value_t!(matches, "year", u16).unwrap_or_else(|e| e.exit()))
+    } else {
+        None
+    };
+    let day = if matches.is_present("day") {
+        Some(value_t!(matches, "day", u8).unwrap_or_else(|e| e.exit()))
+    } else {
+        None
+    };
+    let parts: Vec<u8> = match matches.value_of("part") {
+        Some(part) => vec![part.parse().unwrap()],
+        None => vec![1, 2],
+    };
+
+    (days::select(year, day), parts)
+}
+
+/// Run each selected puzzle and print the answers. Returns false if any puzzle couldn't be run.
+fn run(matches: &ArgMatches) -> bool {
+    let (selected, parts) = selection(matches);
+    if selected.is_empty() {
+        eprintln!("no puzzles found to run");
+        return false;
+    }
+
+    let format = value_t!(matches, "format", Format).unwrap_or_else(|e| e.exit());
+
+    let mut succeeded = true;
+    for puzzle in selected {
+        let answered = read_input(puzzle, matches.value_of("input"))
+            .map_err(PuzzleError::from)
+            .and_then(|input| Ok((puzzle.solve)(&input, &parts)?));
+        // a part that failed is reported as an error, as an input that couldn't be read is.
+        if let Ok(ref answered) = answered {
+            if answered
+                .answers
+                .iter()
+                .any(|(_, answer)| matches!(answer, Answer::Failed(_)))
+            {
+                succeeded = false;
+            }
+        }
+        match (answered, format) {
+            (Ok(answered), Format::Text) => {
+                for (part, answer) in answered.answers {
+                    match answer {
+                        Answer::Failed(reason) => eprintln!(
+                            "{} day {} part {}: {}",
+                            puzzle.year, puzzle.day, part, reason
+                        ),
+                        _ => println!(
+                            "{} day {} part {}: {}",
+                            puzzle.year, puzzle.day, part, answer
+                        ),
+                    }
+                }
+            }
+            (Ok(answered), Format::Json) => print_records(&Record::answered(puzzle, &answered)),
+            (Err(err), Format::Text) => {
+                eprintln!("{} day {}: {}", puzzle.year, puzzle.day, err);
+                succeeded = false;
+            }
+            (Err(err), Format::Json) => {
+                print_records(&Record::failed(puzzle, &parts, &err));
+                succeeded = false;
+            }
+        }
+    }
+    succeeded
+}
+
+/// Verify each selected puzzle against its recorded answers and print the verdicts. Returns false
+/// if any part failed or no longer matches.
+fn verify_answers(matches: &ArgMatches) -> bool {
+    let (selected, parts) = selection(matches);
+    if selected.is_empty() {
+        eprintln!("no puzzles found to verify");
+        return false;
+    }
+
+    let format = value_t!(matches, "format", Format).unwrap_or_else(|e| e.exit());
+
+    let mut failures = 0;
+    for puzzle in selected {
+        let (solved, verdicts) = check(puzzle, &parts);
+        failures += verdicts.iter().filter(|(_, v)| v.is_failure()).count();
+        match format {
+            Format::Text => {
+                for (part, verdict) in verdicts {
+                    println!(
+                        "{} day {} part {}: {}",
+                        puzzle.year, puzzle.day, part, verdict
+                    );
+                }
+            }
+            Format::Json => print_records(&Record::verified(puzzle, solved.as_ref(), &verdicts)),
+        }
+    }
+    if failures > 0 {
+        eprintln!("{} parts failed verification", failures);
+    }
+    failures == 0
+}
+
+/// Write each record on a line of its own.
+fn print_records(records: &[Record]) {
+    for record in records {
+        println!("{}", record.to_json());
+    }
+}
+
+/// Time each selected puzzle and print the timings as a table, comparing them with a baseline
+/// and saving them as one when asked. Returns false if any puzzle couldn't be timed.
+fn summary(matches: &ArgMatches) -> bool {
+    let (selected, parts) = selection(matches);
+    if selected.is_empty() {
+        eprintln!("no puzzles found to time");
+        return false;
+    }
+    let runs = value_t!(matches, "runs", u32).unwrap_or_else(|e| e.exit());
+    let baseline = match matches.value_of("baseline").map(Baseline::from_path) {
+        Some(Ok(baseline)) => Some(baseline),
+        Some(Err(err)) => {
+            eprintln!("{}", err);
+            return false;
+        }
+        None => None,
+    };
+
+    let mut succeeded = true;
+    let mut timed = vec![];
+    for puzzle in selected {
+        match timing::time(puzzle, &parts, runs) {
+            Ok(timings) => timed.push((puzzle, timings)),
+            Err(err) => {
+                eprintln!("{} day {}: {}", puzzle.year, puzzle.day, err);
+                succeeded = false;
+            }
+        }
+    }
+    print!("{}", timing::table(&timed, &parts, baseline.as_ref()));
+
+    if let Some(path) = matches.value_of("save") {
+        if let Err(err) = Baseline::record(&timed).save(path) {
+            eprintln!("couldn't save the baseline to {}: {}", path, err);
+            succeeded = false;
+        }
+    }
+    succeeded
+}
+
+/// Create the crate for a new day. Returns false if it couldn't be created.
+fn new_day(matches: &ArgMatches) -> bool {
+    let scaffold = Scaffold {
+        year: value_t!(matches, "year", u16).unwrap_or_else(|e| e.exit()),
+        day: value_t!(matches, "day", u8).unwrap_or_else(|e| e.exit()),
+        title: matches.value_of("title").map(String::from),
+    };
+
+    match scaffold.create(days::workspace_root()) {
+        Ok(crate_dir) => {
+            println!("{}", crate_dir.display());
+            true
+        }
+        Err(err) => {
+            eprintln!("{} day {}: {}", scaffold.year, scaffold.day, err);
+            false
+        }
+    }
+}
+
+/// Download the input for the selected day into the day's crate. Returns false if it couldn't be
+/// provided.
+fn fetch(matches: &ArgMatches) -> bool {
+    let (selected, _) = selection(matches);
+    let puzzle = match selected.first() {
+        Some(puzzle) => puzzle,
+        None => {
+            eprintln!("no puzzle found to fetch the input for");
+            return false;
+        }
+    };
+
+    let fetched = Fetcher::configure(matches.value_of("session"), matches.value_of("base_url"))
+        .and_then(|fetcher| fetcher.fetch(puzzle.year, puzzle.day, &puzzle.input_path()));
+    match fetched {
+        Ok(_) => {
+            println!("{}", puzzle.input_path().display());
+            true
+        }
+        Err(err) => {
+            eprintln!("{} day {}: {}", puzzle.year, puzzle.day, err);
+            false
+        }
+    }
+}
+
+/// Write a synthetic input for the selected day to stdout. Returns false if it couldn't be written.
+fn generate(matches: &ArgMatches) -> bool {
+    let (selected, _) = selection(matches);
+    let puzzle = match selected.first() {
+        Some(puzzle) => puzzle,
+        None => {
+            eprintln!("no puzzle found to generate an input for");
+            return false;
+        }
+    };
+    let size = if matches.is_present("size") {
+        Size::Records(value_t!(matches, "size", usize).unwrap_or_else(|e| e.exit()))
+    } else if matches.is_present("scale") {
+        Size::Scale(value_t!(matches, "scale", usize).unwrap_or_else(|e| e.exit()))
+    } else {
+        Size::Scale(1)
+    };
+    let seed = value_t!(matches, "seed", u64).unwrap_or_else(|e| e.exit());
+
+    let input = (puzzle.generate)(size, seed);
+    match io::stdout().lock().write_all(input.as_bytes()) {
+        Ok(_) => true,
+        Err(err) => {
+            eprintln!("couldn't write the input: {}", err);
+            false
+        }
+    }
+}
+
+/// Read the input for the day, either from the supplied path or from alongside the day's crate.
+fn read_input(puzzle: &Day, path: Option<&str>) -> Result<Input, InputError> {
+    match path {
+        Some(STDIN_ARG) => Input::from_stdin(),
+        Some(path) => Input::from_path(path),
+        None => Input::from_path(puzzle.input_path()),
+    }
+}
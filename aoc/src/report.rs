@@ -0,0 +1,217 @@
+use crate::days::Day;
+use common::answers::Verdict;
+use common::solution::{Answer, Solved};
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// How results are written out.
+pub enum Format {
+    /// A line of text per part, for people.
+    Text,
+    /// A JSON record per line for each part, for scripts.
+    Json,
+}
+
+impl Format {
+    pub const NAMES: &'static [&'static str] = &["text", "json"];
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format {:?}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+/// The outcome of a single part of a puzzle.
+///
+/// The JSON looks like: -
+///
+/// {"year":2018,"day":4,"part":1,"answer":"106710","parse_ns":1206413,"part_ns":1412,"error":null}
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// The answer, missing when the part is unsolved, failed or couldn't be run.
+    pub answer: Option<String>,
+    /// Time taken to parse the input, shared by every part of the day.
+    pub parse_ns: Option<u64>,
+    pub part_ns: Option<u64>,
+    /// Result of checking the answer against the one recorded, only present when verifying.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verdict: Option<String>,
+    pub error: Option<String>,
+}
+
+impl Record {
+    /// A record for each part that was answered.
+    pub fn answered(puzzle: &Day, solved: &Solved) -> Vec<Record> {
+        solved
+            .answers
+            .iter()
+            .map(|(part, answer)| Record {
+                year: puzzle.year,
+                day: puzzle.day,
+                part: *part,
+                answer: match answer {
+                    Answer::Unsolved | Answer::Failed(_) => None,
+                    _ => Some(answer.to_string()),
+                },
+                parse_ns: Some(solved.timings.parse.as_nanos() as u64),
+                part_ns: solved.timings.part(*part).map(|d| d.as_nanos() as u64),
+                verdict: None,
+                error: match answer {
+                    Answer::Failed(reason) => Some(reason.clone()),
+                    _ => None,
+                },
+            })
+            .collect()
+    }
+
+    /// A record for each part that couldn't be run.
+    pub fn failed<E: fmt::Display>(puzzle: &Day, parts: &[u8], err: &E) -> Vec<Record> {
+        parts
+            .iter()
+            .map(|part| Record {
+                error: Some(err.to_string()),
+                ..Record::empty(puzzle, *part)
+            })
+            .collect()
+    }
+
+    /// A record for each part checked against its recorded answer.
+    pub fn verified(
+        puzzle: &Day,
+        solved: Option<&Solved>,
+        verdicts: &[(u8, Verdict)],
+    ) -> Vec<Record> {
+        let answered = solved
+            .map(|solved| Record::answered(puzzle, solved))
+            .unwrap_or_default();
+
+        verdicts
+            .iter()
+            .map(|(part, verdict)| {
+                let record = match answered.iter().find(|record| record.part == *part) {
+                    Some(record) => record.clone(),
+                    None => Record {
+                        error: match verdict {
+                            Verdict::Fail(reason) => Some(reason.clone()),
+                            _ => None,
+                        },
+                        ..Record::empty(puzzle, *part)
+                    },
+                };
+                Record {
+                    verdict: Some(verdict.to_string()),
+                    ..record
+                }
+            })
+            .collect()
+    }
+
+    fn empty(puzzle: &Day, part: u8) -> Record {
+        Record {
+            year: puzzle.year,
+            day: puzzle.day,
+            part,
+            answer: None,
+            parse_ns: None,
+            part_ns: None,
+            verdict: None,
+            error: None,
+        }
+    }
+
+    /// The record as a single line of JSON.
+    pub fn to_json(&self) -> String {
+        // Every field is a plain number or string so this can't fail.
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+#[test]
+fn test_format_from_str() {
+    assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+    assert!("yaml".parse::<Format>().is_err());
+}
+
+#[test]
+fn test_answered_records() {
+    use common::solution::Timings;
+    use std::time::Duration;
+
+    let puzzle = &crate::days::DAYS[3];
+    let solved = Solved {
+        answers: vec![(1, Answer::Number(106710)), (2, Answer::Unsolved)],
+        timings: Timings {
+            parse: Duration::from_nanos(1500),
+            parts: vec![(1, Duration::from_nanos(20)), (2, Duration::from_nanos(3))],
+        },
+    };
+    let records = Record::answered(puzzle, &solved);
+    assert_eq!(
+        records[0].to_json(),
+        r#"{"year":2018,"day":4,"part":1,"answer":"106710","parse_ns":1500,"part_ns":20,"error":null}"#
+    );
+    assert_eq!(records[1].answer, None);
+
+    let solved = Solved {
+        answers: vec![(1, Answer::Failed("overflowed".to_string()))],
+        timings: Timings::default(),
+    };
+    let records = Record::answered(puzzle, &solved);
+    assert_eq!(records[0].answer, None);
+    assert_eq!(records[0].error, Some("overflowed".to_string()));
+}
+
+#[test]
+fn test_failed_records() {
+    let puzzle = &crate::days::DAYS[3];
+    let records = Record::failed(puzzle, &[1, 2], &"line 3: unknown event");
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[1].to_json(),
+        r#"{"year":2018,"day":4,"part":2,"answer":null,"parse_ns":null,"part_ns":null,"error":"line 3: unknown event"}"#
+    );
+}
+
+#[test]
+fn test_verified_records() {
+    use common::solution::Timings;
+
+    let puzzle = &crate::days::DAYS[3];
+    let solved = Solved {
+        answers: vec![(1, Answer::Number(13))],
+        timings: Timings::default(),
+    };
+    let verdicts = vec![(
+        1,
+        Verdict::Mismatch {
+            expected: "12".to_string(),
+            actual: Answer::Number(13),
+        },
+    )];
+    let records = Record::verified(puzzle, Some(&solved), &verdicts);
+    assert_eq!(records[0].answer, Some("13".to_string()));
+    assert_eq!(
+        records[0].verdict,
+        Some("mismatch (expected 12, got 13)".to_string())
+    );
+
+    let verdicts = vec![(2, Verdict::Fail("couldn't read input".to_string()))];
+    let records = Record::verified(puzzle, None, &verdicts);
+    assert_eq!(records[0].error, Some("couldn't read input".to_string()));
+    assert_eq!(
+        records[0].to_json(),
+        r#"{"year":2018,"day":4,"part":2,"answer":null,"parse_ns":null,"part_ns":null,"verdict":"fail (couldn't read input)","error":"couldn't read input"}"#
+    );
+}
@@ -0,0 +1,476 @@
+use common::answers::ANSWERS_FILE_NAME;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A new day to add to the workspace.
+pub struct Scaffold {
+    pub year: u16,
+    pub day: u8,
+    /// Title of the puzzle, used to name the solution. Falls back to the day number.
+    pub title: Option<String>,
+}
+
+impl Scaffold {
+    /// Folder within the year for the new crate. Days are named the same way as the others in the
+    /// year, a year without any days yet uses the newer `dayN` style.
+    pub fn dir(&self, root: &Path) -> String {
+        let year_dir = root.join(self.year.to_string());
+        let short_names = fs::read_dir(year_dir)
+            .map(|entries| {
+                entries.filter_map(|e| e.ok()).any(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    name.starts_with('d') && !name.starts_with("day")
+                })
+            })
+            .unwrap_or(false);
+
+        if short_names {
+            format!("d{}", self.day)
+        } else {
+            format!("day{}", self.day)
+        }
+    }
+
+    /// Name of the crate. This is the folder name unless another year already has a crate by that
+    /// name, in which case the year is added to tell them apart.
+    pub fn crate_name(&self, root: &Path) -> String {
+        let dir = self.dir(root);
+        let manifest = fs::read_to_string(root.join("aoc").join("Cargo.toml")).unwrap_or_default();
+        let taken = manifest
+            .lines()
+            .any(|line| line.starts_with(&format!("{} = ", dir)));
+        if taken {
+            format!("{}_{}", dir, self.year)
+        } else {
+            dir
+        }
+    }
+
+    /// Name of the type implementing the solution, taken from the title.
+    ///
+    /// The title looks like: -
+    ///
+    /// Binary Boarding
+    pub fn solution_name(&self) -> String {
+        let name: String = self
+            .title
+            .as_deref()
+            .unwrap_or("")
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            })
+            .collect();
+
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("Day{}{}", self.day, name)
+        } else {
+            name
+        }
+    }
+
+    /// Create the crate for the day and register it with the workspace and the runner. Returns
+    /// where the crate was created.
+    pub fn create(&self, root: &Path) -> Result<PathBuf, ScaffoldError> {
+        if !(1..=25).contains(&self.day) {
+            return Err(ScaffoldError::NoSuchDay(self.day));
+        }
+        let dir = self.dir(root);
+        let crate_dir = root.join(self.year.to_string()).join(&dir);
+        if crate_dir.exists() {
+            return Err(ScaffoldError::Exists(crate_dir));
+        }
+        let crate_name = self.crate_name(root);
+        let solution = self.solution_name();
+
+        fs::create_dir_all(crate_dir.join("src"))?;
+        fs::create_dir_all(crate_dir.join("benches"))?;
+        fs::write(crate_dir.join("Cargo.toml"), manifest(&crate_name))?;
+        fs::write(crate_dir.join("src").join("lib.rs"), self.lib(&solution))?;
+        fs::write(
+            crate_dir.join("src").join("main.rs"),
+            main(&crate_name, &solution),
+        )?;
+        fs::write(
+            crate_dir.join("benches").join("solver.rs"),
+            bench(&crate_name, &solution),
+        )?;
+        fs::write(crate_dir.join(ANSWERS_FILE_NAME), "part 1:\npart 2:\n")?;
+
+        self.register_member(root)?;
+        self.register_dependency(root, &crate_name, &dir)?;
+        self.register_day(root, &crate_name, &dir, &solution)?;
+
+        Ok(crate_dir)
+    }
+
+    fn lib(&self, solution: &str) -> String {
+        let title = match &self.title {
+            Some(title) => format!("Day {}: {}.", self.day, title),
+            None => format!("Day {}.", self.day),
+        };
+        LIB_TEMPLATE
+            .replace("{title}", &title)
+            .replace("{solution}", solution)
+    }
+
+    /// Make sure the year's folder is one of the workspace members.
+    fn register_member(&self, root: &Path) -> Result<(), ScaffoldError> {
+        let path = root.join("Cargo.toml");
+        let manifest = fs::read_to_string(&path)?;
+        let member = format!("\"{}/*\"", self.year);
+        if manifest.contains(&member) {
+            return Ok(());
+        }
+
+        let members = match manifest.find("members = [") {
+            Some(start) => start,
+            None => return Err(ScaffoldError::Unregistered(path)),
+        };
+        let end = match manifest[members..].find(']') {
+            Some(end) => members + end,
+            None => return Err(ScaffoldError::Unregistered(path)),
+        };
+        let updated = format!("{}, {}{}", &manifest[..end], member, &manifest[end..]);
+        fs::write(path, updated)?;
+        Ok(())
+    }
+
+    /// Add the crate as a dependency of the runner, after the other days.
+    fn register_dependency(
+        &self,
+        root: &Path,
+        crate_name: &str,
+        dir: &str,
+    ) -> Result<(), ScaffoldError> {
+        let path = root.join("aoc").join("Cargo.toml");
+        let manifest = fs::read_to_string(&path)?;
+        let dependency = format!("{} = {{ path = \"../{}/{}\" }}", crate_name, self.year, dir);
+
+        let mut lines: Vec<&str> = manifest.lines().collect();
+        let last_day = match lines
+            .iter()
+            .rposition(|line| line.contains("path = \"../20"))
+        {
+            Some(last_day) => last_day,
+            None => return Err(ScaffoldError::Unregistered(path)),
+        };
+        lines.insert(last_day + 1, &dependency);
+        fs::write(path, lines.join("\n") + "\n")?;
+        Ok(())
+    }
+
+    /// Add the day to the runner's list, keeping the list in order of year then day.
+    fn register_day(
+        &self,
+        root: &Path,
+        crate_name: &str,
+        dir: &str,
+        solution: &str,
+    ) -> Result<(), ScaffoldError> {
+        let path = root.join("aoc").join("src").join("days.rs");
+        let source = fs::read_to_string(&path)?;
+        let (start, end) = match source.find("pub const DAYS: &[Day] = &[\n") {
+            Some(start) => match source[start..].find("\n];") {
+                Some(end) => (start, start + end + 1),
+                None => return Err(ScaffoldError::Unregistered(path)),
+            },
+            None => return Err(ScaffoldError::Unregistered(path)),
+        };
+
+        // The first entry for a later day is where the new day belongs.
+        let mut position = end;
+        let mut offset = start;
+        while let Some(found) = source[offset..end].find("    Day {\n") {
+            let entry = offset + found;
+            if entry_key(&source[entry..end]) > Some((self.year, self.day)) {
+                position = entry;
+                break;
+            }
+            offset = entry + 1;
+        }
+
+        let entry = format!(
+            "    Day {{\n        year: {year},\n        day: {day},\n        dir: \"{dir}\",\n        solve: solve::<{krate}::{solution}>,\n        time: time::<{krate}::{solution}>,\n        generate: generate::<{krate}::{solution}>,\n    }},\n",
+            year = self.year,
+            day = self.day,
+            dir = dir,
+            krate = crate_name,
+            solution = solution
+        );
+        let updated = format!("{}{}{}", &source[..position], entry, &source[position..]);
+        fs::write(path, updated)?;
+        Ok(())
+    }
+}
+
+/// The year and day of a `Day` entry in the runner's list.
+fn entry_key(entry: &str) -> Option<(u16, u8)> {
+    let field = |name: &str| {
+        entry
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(name))
+            .map(|value| value.trim_end_matches(',').trim().to_string())
+    };
+    let year = field("year:")?.parse().ok()?;
+    let day = field("day:")?.parse().ok()?;
+    Some((year, day))
+}
+
+fn manifest(crate_name: &str) -> String {
+    MANIFEST_TEMPLATE.replace("{name}", crate_name)
+}
+
+fn main(crate_name: &str, solution: &str) -> String {
+    MAIN_TEMPLATE
+        .replace("{crate}", crate_name)
+        .replace("{solution}", solution)
+}
+
+fn bench(crate_name: &str, solution: &str) -> String {
+    BENCH_TEMPLATE
+        .replace("{crate}", crate_name)
+        .replace("{solution}", solution)
+}
+
+const MANIFEST_TEMPLATE: &str = r#"[package]
+name = "{name}"
+version = "0.1.0"
+authors = ["Jonathan Pulfer <pulfer@gmail.com>"]
+edition = "2018"
+
+[dependencies]
+common = { path = "../../common" }
+
+[dev-dependencies]
+common = { path = "../../common", features = ["bench"] }
+
+[[bench]]
+name = "solver"
+harness = false
+"#;
+
+const LIB_TEMPLATE: &str = r#"use common::generate::{Generate, Rng};
+use common::input::Input;
+use common::parse::ParseError;
+use common::solution::{Answer, Solution};
+
+/// {title}
+pub struct {solution};
+
+impl Solution for {solution} {
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn parse(input: &Input) -> Result<Vec<&str>, ParseError> {
+        Ok(input.lines().collect())
+    }
+
+    fn part1(_lines: &Vec<&str>) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part2(_lines: &Vec<&str>) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+impl Generate for {solution} {
+    const REAL_SIZE: usize = 0;
+
+    fn generate(_size: usize, _rng: &mut Rng) -> String {
+        String::new()
+    }
+}
+
+/// The example given in the puzzle description.
+#[cfg(test)]
+const EXAMPLE: &str = "";
+
+#[test]
+fn test_example() {
+    let input = Input::new(EXAMPLE);
+    let parsed = {solution}::parse(&input).unwrap();
+    assert_eq!({solution}::part1(&parsed), Answer::Unsolved);
+    assert_eq!({solution}::part2(&parsed), Answer::Unsolved);
+}
+"#;
+
+const MAIN_TEMPLATE: &str = r#"use common::input::Input;
+use common::solution::print_answers;
+use {crate}::{solution};
+use std::process;
+
+fn main() {
+    let input = Input::from_args().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+
+    if let Err(err) = print_answers::<{solution}>(&input) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+"#;
+
+const BENCH_TEMPLATE: &str = r#"use common::bench::bench_solution;
+use {crate}::{solution};
+
+fn main() {
+    bench_solution::<{solution}>("{crate}");
+}
+"#;
+
+#[derive(Debug)]
+/// Failure to add a new day to the workspace.
+pub enum ScaffoldError {
+    /// Puzzles only run from day 1 to day 25.
+    NoSuchDay(u8),
+    /// The crate for the day is already there.
+    Exists(PathBuf),
+    /// The file the day needs registering in isn't laid out as expected.
+    Unregistered(PathBuf),
+    Io(io::Error),
+}
+
+impl From<io::Error> for ScaffoldError {
+    fn from(err: io::Error) -> ScaffoldError {
+        ScaffoldError::Io(err)
+    }
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScaffoldError::NoSuchDay(day) => write!(f, "there's no day {}, days run 1 to 25", day),
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Unregistered(path) => {
+                write!(
+                    f,
+                    "couldn't find where to register the day in {}",
+                    path.display()
+                )
+            }
+            ScaffoldError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl error::Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ScaffoldError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+/// A copy of the parts of the workspace the scaffold touches, somewhere it is safe to change them.
+fn test_workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("aoc-scaffold-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let real = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+
+    fs::create_dir_all(root.join("aoc").join("src")).unwrap();
+    fs::create_dir_all(root.join("2018").join("d1")).unwrap();
+    fs::create_dir_all(root.join("2020").join("day1")).unwrap();
+    for file in &["Cargo.toml", "aoc/Cargo.toml", "aoc/src/days.rs"] {
+        fs::copy(real.join(file), root.join(file)).unwrap();
+    }
+    root
+}
+
+#[test]
+fn test_solution_name() {
+    let mut scaffold = Scaffold {
+        year: 2020,
+        day: 5,
+        title: Some("Binary Boarding".to_string()),
+    };
+    assert_eq!(scaffold.solution_name(), "BinaryBoarding");
+    scaffold.title = Some("1202 program alarm".to_string());
+    assert_eq!(scaffold.solution_name(), "Day51202ProgramAlarm");
+    scaffold.title = None;
+    assert_eq!(scaffold.solution_name(), "Day5");
+}
+
+#[test]
+fn test_names_follow_the_year() {
+    let root = test_workspace("names");
+    let in_2018 = Scaffold {
+        year: 2018,
+        day: 8,
+        title: None,
+    };
+    assert_eq!(in_2018.dir(&root), "d8");
+    assert_eq!(in_2018.crate_name(&root), "d8");
+
+    let in_2021 = Scaffold {
+        year: 2021,
+        day: 1,
+        title: None,
+    };
+    assert_eq!(in_2021.dir(&root), "day1");
+    assert_eq!(in_2021.crate_name(&root), "day1_2021");
+}
+
+#[test]
+fn test_create() {
+    let root = test_workspace("create");
+    let scaffold = Scaffold {
+        year: 2018,
+        day: 8,
+        title: Some("Memory Maneuver".to_string()),
+    };
+    let crate_dir = scaffold.create(&root).unwrap();
+    assert_eq!(crate_dir, root.join("2018").join("d8"));
+
+    let lib = fs::read_to_string(crate_dir.join("src").join("lib.rs")).unwrap();
+    assert!(lib.contains("/// Day 8: Memory Maneuver.\npub struct MemoryManeuver;"));
+    assert_eq!(
+        fs::read_to_string(crate_dir.join(ANSWERS_FILE_NAME)).unwrap(),
+        "part 1:\npart 2:\n"
+    );
+
+    let manifest = fs::read_to_string(root.join("aoc").join("Cargo.toml")).unwrap();
+    assert!(manifest.contains("d7 = { path = \"../2018/d7\" }\nday1 = "));
+    assert!(manifest.ends_with("d8 = { path = \"../2018/d8\" }\n"));
+
+    // The new day goes after the rest of 2018 but before 2020.
+    let days = fs::read_to_string(root.join("aoc").join("src").join("days.rs")).unwrap();
+    let new_day = days.find("solve: solve::<d8::MemoryManeuver>").unwrap();
+    assert!(new_day > days.find("solve: solve::<d7::SumOfItsParts>").unwrap());
+    assert!(new_day < days.find("solve: solve::<day1::ReportRepair>").unwrap());
+
+    match scaffold.create(&root) {
+        Err(ScaffoldError::Exists(_)) => {}
+        other => panic!("expected the day to exist already, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_create_new_year() {
+    let root = test_workspace("year");
+    let scaffold = Scaffold {
+        year: 2021,
+        day: 1,
+        title: None,
+    };
+    scaffold.create(&root).unwrap();
+
+    let workspace = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+    assert!(workspace.contains("\"2020/*\", \"2021/*\"]"));
+    let days = fs::read_to_string(root.join("aoc").join("src").join("days.rs")).unwrap();
+    assert!(days.contains(
+        "time: time::<day1_2021::Day1>,\n        generate: generate::<day1_2021::Day1>,\n    },\n];"
+    ));
+}
@@ -0,0 +1,233 @@
+use crate::days::{Day, PuzzleError};
+use common::input::{Input, InputError};
+use common::solution::Timings;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// Name each stage of a puzzle is reported and saved under.
+pub const PARSE_STAGE: &str = "parse";
+
+/// Time the requested parts of the day against its real input.
+pub fn time(puzzle: &Day, parts: &[u8], runs: u32) -> Result<Timings, PuzzleError> {
+    let input = Input::from_path(puzzle.input_path())?;
+    Ok((puzzle.time)(&input, parts, runs)?)
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+/// Timings saved from an earlier summary so that later ones can be compared against them. Each
+/// stage is saved in nanoseconds.
+///
+/// The file looks like: -
+///
+/// 2018 day 4 parse: 1530211
+/// 2018 day 4 part 1: 20417
+/// 2018 day 4 part 2: 19873
+pub struct Baseline {
+    stages: HashMap<(u16, u8, String), Duration>,
+}
+
+impl Baseline {
+    /// Read a saved baseline from the file at the provided path.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Baseline, InputError> {
+        Ok(Baseline::parse(&Input::from_path(path)?))
+    }
+
+    /// Pick out the time saved for each stage. Lines that aren't in the expected format are
+    /// ignored.
+    pub fn parse(input: &Input) -> Baseline {
+        let mut stages: HashMap<(u16, u8, String), Duration> = HashMap::new();
+        for line in input.lines() {
+            let (label, nanos) = match line.split_once(':') {
+                Some(parts) => parts,
+                None => continue,
+            };
+            let label: Vec<&str> = label.split_whitespace().collect();
+            if label.len() < 4 || label[1] != "day" {
+                continue;
+            }
+            if let (Ok(year), Ok(day), Ok(nanos)) = (
+                label[0].parse::<u16>(),
+                label[2].parse::<u8>(),
+                nanos.trim().parse::<u64>(),
+            ) {
+                let stage = label[3..].join(" ");
+                stages.insert((year, day, stage), Duration::from_nanos(nanos));
+            }
+        }
+        Baseline { stages }
+    }
+
+    /// Record the timings for each puzzle as the new baseline.
+    pub fn record(summary: &[(&Day, Timings)]) -> Baseline {
+        let mut stages: HashMap<(u16, u8, String), Duration> = HashMap::new();
+        for (puzzle, timings) in summary {
+            for (stage, taken) in stages_of(timings) {
+                stages.insert((puzzle.year, puzzle.day, stage), taken);
+            }
+        }
+        Baseline { stages }
+    }
+
+    /// Save the baseline to the file at the provided path.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// The time saved for a stage of the day, if there is one.
+    pub fn stage(&self, year: u16, day: u8, stage: &str) -> Option<Duration> {
+        self.stages.get(&(year, day, stage.to_string())).copied()
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut stages: Vec<_> = self.stages.iter().collect();
+        stages.sort_by(|a, b| a.0.cmp(b.0));
+        for ((year, day, stage), taken) in stages {
+            writeln!(f, "{} day {} {}: {}", year, day, stage, taken.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// Each stage that was timed along with its name.
+fn stages_of(timings: &Timings) -> Vec<(String, Duration)> {
+    let mut stages = vec![(PARSE_STAGE.to_string(), timings.parse)];
+    for (part, taken) in &timings.parts {
+        stages.push((format!("part {}", part), *taken));
+    }
+    stages
+}
+
+/// Lay out the timings for each puzzle as a table, one row per day. When a baseline is provided
+/// each stage also shows how much it has changed by.
+pub fn table(summary: &[(&Day, Timings)], parts: &[u8], baseline: Option<&Baseline>) -> String {
+    let mut header = vec![
+        "year".to_string(),
+        "day".to_string(),
+        PARSE_STAGE.to_string(),
+    ];
+    for part in parts {
+        header.push(format!("part {}", part));
+    }
+    header.push("total".to_string());
+
+    let mut rows = vec![header];
+    let mut overall = Duration::default();
+    for (puzzle, timings) in summary {
+        let mut row = vec![puzzle.year.to_string(), puzzle.day.to_string()];
+        for (stage, taken) in stages_of(timings) {
+            let saved = baseline.and_then(|b| b.stage(puzzle.year, puzzle.day, &stage));
+            row.push(cell(taken, saved));
+        }
+        row.push(format_duration(timings.total()));
+        overall += timings.total();
+        rows.push(row);
+    }
+    let mut footer = vec![String::new(); rows[0].len() - 1];
+    footer[0] = "total".to_string();
+    footer.push(format_duration(overall));
+    rows.push(footer);
+
+    let columns = rows[0].len();
+    let widths: Vec<usize> = (0..columns)
+        .map(|c| rows.iter().map(|r| r[c].chars().count()).max().unwrap_or(0))
+        .collect();
+
+    let mut table = String::new();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// A stage's time, followed by its change from the baseline when there is one to compare with.
+fn cell(taken: Duration, saved: Option<Duration>) -> String {
+    match saved {
+        Some(saved) if !saved.is_zero() => {
+            let change = (taken.as_secs_f64() / saved.as_secs_f64() - 1.0) * 100.0;
+            format!("{} ({:+.0}%)", format_duration(taken), change)
+        }
+        _ => format_duration(taken),
+    }
+}
+
+/// A duration in whichever unit keeps it readable.
+fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1_000.0)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1_000_000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+/// Timings for a puzzle that took a millisecond to parse and two for each part.
+fn test_timings() -> Timings {
+    Timings {
+        parse: Duration::from_millis(1),
+        parts: vec![(1, Duration::from_millis(2)), (2, Duration::from_millis(2))],
+    }
+}
+
+#[test]
+fn test_baseline_round_trip() {
+    let puzzle = &crate::days::DAYS[3];
+    let baseline = Baseline::record(&[(puzzle, test_timings())]);
+    assert_eq!(
+        baseline.to_string(),
+        "2018 day 4 parse: 1000000\n2018 day 4 part 1: 2000000\n2018 day 4 part 2: 2000000\n"
+    );
+    assert_eq!(
+        Baseline::parse(&Input::new(&baseline.to_string())),
+        baseline
+    );
+}
+
+#[test]
+fn test_baseline_skips_other_lines() {
+    let baseline = Baseline::parse(&Input::new("# saved\n2018 day 4 part 1: 10\n2018: 4\n"));
+    assert_eq!(
+        baseline.stage(2018, 4, "part 1"),
+        Some(Duration::from_nanos(10))
+    );
+    assert_eq!(baseline.stages.len(), 1);
+}
+
+#[test]
+fn test_table_compares_with_baseline() {
+    let puzzle = &crate::days::DAYS[3];
+    let baseline = Baseline::parse(&Input::new("2018 day 4 part 1: 1000000"));
+    let table = table(&[(puzzle, test_timings())], &[1, 2], Some(&baseline));
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+        lines[0].split_whitespace().collect::<Vec<&str>>(),
+        vec!["year", "day", "parse", "part", "1", "part", "2", "total"]
+    );
+    assert!(lines[1].contains("2.00ms (+100%)"));
+    assert!(lines[2].trim_start().starts_with("total"));
+    assert!(lines[2].ends_with("5.00ms"));
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+    assert_eq!(format_duration(Duration::from_micros(1500)), "1.50ms");
+    assert_eq!(format_duration(Duration::from_millis(2500)), "2.50s");
+}
@@ -0,0 +1,47 @@
+use crate::days::{Day, PuzzleError};
+use common::answers::{Answers, Verdict};
+use common::input::Input;
+use common::solution::Solved;
+
+/// Run the requested parts of the day against its real input and check each answer against the
+/// ones recorded alongside it.
+pub fn verify(puzzle: &Day, parts: &[u8]) -> Vec<(u8, Verdict)> {
+    check(puzzle, parts).1
+}
+
+/// Verify the day as `verify` does, also handing back the answers and how long they took when the
+/// day could be run.
+pub fn check(puzzle: &Day, parts: &[u8]) -> (Option<Solved>, Vec<(u8, Verdict)>) {
+    // Not every day has its input committed, there's nothing to verify for those.
+    if !puzzle.input_path().exists() {
+        let verdicts = parts
+            .iter()
+            .map(|part| (*part, Verdict::Skipped("no input committed".to_string())))
+            .collect();
+        return (None, verdicts);
+    }
+    let answered = match solve(puzzle, parts) {
+        Ok(answered) => answered,
+        Err(err) => {
+            let verdicts = parts
+                .iter()
+                .map(|part| (*part, Verdict::Fail(err.to_string())))
+                .collect();
+            return (None, verdicts);
+        }
+    };
+    // A day without an answers file simply has nothing recorded yet.
+    let answers = Answers::from_path(puzzle.answers_path()).unwrap_or_default();
+
+    let verdicts = answered
+        .answers
+        .iter()
+        .map(|(part, answer)| (*part, answers.check(*part, answer)))
+        .collect();
+    (Some(answered), verdicts)
+}
+
+fn solve(puzzle: &Day, parts: &[u8]) -> Result<Solved, PuzzleError> {
+    let input = Input::from_path(puzzle.input_path())?;
+    Ok((puzzle.solve)(&input, parts)?)
+}
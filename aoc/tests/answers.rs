@@ -0,0 +1,20 @@
+use aoc::days::DAYS;
+use aoc::verify::verify;
+
+/// Every day with a committed input must still produce the answers recorded for it. Days without
+/// one are skipped by `verify`.
+#[test]
+fn test_recorded_answers() {
+    let mut failures: Vec<String> = vec![];
+    for puzzle in DAYS {
+        for (part, verdict) in verify(puzzle, &[1, 2]) {
+            if verdict.is_failure() {
+                failures.push(format!(
+                    "{} day {} part {}: {}",
+                    puzzle.year, puzzle.day, part, verdict
+                ));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
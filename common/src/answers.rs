@@ -0,0 +1,142 @@
+use crate::input::{Input, InputError};
+use crate::solution::Answer;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// File name each puzzle crate keeps its recorded answers in, next to its input.
+pub const ANSWERS_FILE_NAME: &str = "answers";
+
+#[derive(Debug, Clone, PartialEq, Default)]
+/// The answers known to be correct for a day's real input. These are recorded in the same format
+/// the day binaries print them in so the output of a run can be saved as the answers file.
+///
+/// The file looks like: -
+///
+/// part 1: 4693
+/// part 2: pebjqsalrdnckzfihvtxysomg
+pub struct Answers {
+    expected: HashMap<u8, String>,
+}
+
+impl Answers {
+    /// Read the recorded answers from the file at the provided path.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Answers, InputError> {
+        Ok(Answers::parse(&Input::from_path(path)?))
+    }
+
+    /// Pick out the answer recorded for each part. Lines that don't name a part are ignored, as
+    /// are parts without an answer filled in yet.
+    pub fn parse(input: &Input) -> Answers {
+        let mut expected: HashMap<u8, String> = HashMap::new();
+        for line in input.lines() {
+            let (label, answer) = match line.split_once(':') {
+                Some(parts) => parts,
+                None => continue,
+            };
+            let part = match label.trim().strip_prefix("part") {
+                Some(number) => number.trim().parse::<u8>(),
+                None => continue,
+            };
+            if let Ok(part) = part {
+                if !answer.trim().is_empty() {
+                    expected.insert(part, answer.trim().to_string());
+                }
+            }
+        }
+        Answers { expected }
+    }
+
+    /// The answer recorded for the part, if there is one.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        self.expected.get(&part).map(|answer| answer.as_str())
+    }
+
+    /// Compare the answer produced for a part with the one recorded.
+    pub fn check(&self, part: u8, actual: &Answer) -> Verdict {
+        match (self.expected(part), actual) {
+            (_, Answer::Failed(reason)) => Verdict::Fail(reason.clone()),
+            (None, _) => Verdict::Unrecorded(actual.clone()),
+            (Some(expected), Answer::Unsolved) => Verdict::Mismatch {
+                expected: expected.to_string(),
+                actual: actual.clone(),
+            },
+            (Some(expected), _) if expected == actual.to_string() => Verdict::Pass,
+            (Some(expected), _) => Verdict::Mismatch {
+                expected: expected.to_string(),
+                actual: actual.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Outcome of checking a part against its recorded answer.
+pub enum Verdict {
+    Pass,
+    Mismatch { expected: String, actual: Answer },
+    /// There's no answer recorded to check against.
+    Unrecorded(Answer),
+    /// The part wasn't run, for instance because the day has no input committed to run it on.
+    Skipped(String),
+    /// The part couldn't be run, for instance because its input couldn't be read.
+    Fail(String),
+}
+
+impl Verdict {
+    /// Whether the verdict should be treated as a regression.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Mismatch { .. } | Verdict::Fail(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Mismatch { expected, actual } => {
+                write!(f, "mismatch (expected {}, got {})", expected, actual)
+            }
+            Verdict::Unrecorded(actual) => write!(f, "unrecorded (got {})", actual),
+            Verdict::Skipped(reason) => write!(f, "skipped ({})", reason),
+            Verdict::Fail(reason) => write!(f, "fail ({})", reason),
+        }
+    }
+}
+
+#[test]
+fn test_parse_answers() {
+    let answers = Answers::parse(&Input::new("part 1: 4693\npart 2: CABDFE\n"));
+    assert_eq!(answers.expected(1), Some("4693"));
+    assert_eq!(answers.expected(2), Some("CABDFE"));
+}
+
+#[test]
+fn test_parse_answers_skips_empty_entries() {
+    let answers = Answers::parse(&Input::new("part 1:\npart 2: 12\nnotes here\n"));
+    assert_eq!(answers.expected(1), None);
+    assert_eq!(answers.expected(2), Some("12"));
+}
+
+#[test]
+fn test_check_answers() {
+    let answers = Answers::parse(&Input::new("part 1: 4693\npart 2: 12"));
+    assert_eq!(answers.check(1, &Answer::Number(4693)), Verdict::Pass);
+    assert_eq!(
+        answers.check(2, &Answer::Number(13)),
+        Verdict::Mismatch {
+            expected: "12".to_string(),
+            actual: Answer::Number(13)
+        }
+    );
+    assert_eq!(
+        answers.check(3, &Answer::Unsolved),
+        Verdict::Unrecorded(Answer::Unsolved)
+    );
+    assert!(answers.check(2, &Answer::Unsolved).is_failure());
+    assert_eq!(
+        answers.check(3, &Answer::Failed("overflowed".to_string())),
+        Verdict::Fail("overflowed".to_string())
+    );
+    assert!(!Verdict::Skipped("no input".to_string()).is_failure());
+}
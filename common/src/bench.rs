@@ -0,0 +1,37 @@
+use crate::input::{Input, DEFAULT_FILE_NAME};
+use crate::solution::Solution;
+use criterion::{black_box, Criterion};
+
+/// Benchmark parsing the real input and each part of the puzzle separately. This is the whole of
+/// each day's bench target, it expects to be run from the day's crate where the input lives.
+pub fn bench_solution<S: Solution>(name: &str) {
+    let input = match Input::from_path(DEFAULT_FILE_NAME) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("{}: nothing to benchmark, {}", name, err);
+            return;
+        }
+    };
+
+    let parsed = match S::parse(&input) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}: nothing to benchmark, {}", name, err);
+            return;
+        }
+    };
+
+    let mut criterion = Criterion::default().configure_from_args();
+    let mut group = criterion.benchmark_group(name);
+    group.bench_function("parse", |b| {
+        b.iter(|| {
+            let _ = S::parse(black_box(&input));
+        })
+    });
+
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+    group.finish();
+
+    criterion.final_summary();
+}
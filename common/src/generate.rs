@@ -0,0 +1,117 @@
+/// A small, seeded source of random numbers for building synthetic inputs. The same seed always
+/// gives the same input so a slow run can be reproduced, which is also why this doesn't lean on a
+/// crate whose sequences may change between versions.
+///
+/// This is SplitMix64, it is quick and good enough to make puzzle inputs but nothing more.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0` up to but not including `bound`, which must be above 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// A number between `low` and `high` inclusive.
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below((high - low) as u64 + 1) as i64
+    }
+
+    /// True roughly once in every `times` calls.
+    pub fn one_in(&mut self, times: u64) -> bool {
+        self.below(times) == 0
+    }
+
+    /// One of the choices, which mustn't be empty.
+    pub fn pick<'a, T>(&mut self, choices: &'a [T]) -> &'a T {
+        &choices[self.below(choices.len() as u64) as usize]
+    }
+
+    /// Put the items in a random order.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// Builds synthetic inputs for a puzzle, in the same format as the real input so they can be fed
+/// straight to the solution. Used to see how a solution copes with far more input than it was
+/// written for.
+pub trait Generate {
+    /// How many records, lines or whatever the input counts in, the real input holds.
+    const REAL_SIZE: usize;
+
+    /// An input holding `size` records.
+    fn generate(size: usize, rng: &mut Rng) -> String;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// How big a synthetic input should be.
+pub enum Size {
+    /// Exactly this many records.
+    Records(usize),
+    /// This many times the size of the real input.
+    Scale(usize),
+}
+
+impl Size {
+    /// The number of records for a puzzle whose real input holds `real_size`.
+    pub fn records(self, real_size: usize) -> usize {
+        match self {
+            Size::Records(records) => records,
+            Size::Scale(scale) => real_size * scale,
+        }
+    }
+}
+
+/// Build an input of the given size for the puzzle from the seed.
+pub fn generate<G: Generate>(size: Size, seed: u64) -> String {
+    G::generate(size.records(G::REAL_SIZE), &mut Rng::new(seed))
+}
+
+#[test]
+fn test_same_seed_same_numbers() {
+    let mut first = Rng::new(2018);
+    let mut second = Rng::new(2018);
+    for _ in 0..10 {
+        assert_eq!(first.next_u64(), second.next_u64());
+    }
+    assert_ne!(Rng::new(2018).next_u64(), Rng::new(2020).next_u64());
+}
+
+#[test]
+fn test_between_is_inclusive() {
+    let mut rng = Rng::new(7);
+    let rolls: Vec<i64> = (0..200).map(|_| rng.between(-1, 1)).collect();
+    assert!(rolls.iter().all(|roll| (-1..=1).contains(roll)));
+    assert!(rolls.contains(&-1) && rolls.contains(&1));
+}
+
+#[test]
+fn test_shuffle_keeps_every_item() {
+    let mut items: Vec<u32> = (0..50).collect();
+    Rng::new(3).shuffle(&mut items);
+    assert_ne!(items, (0..50).collect::<Vec<u32>>());
+    items.sort();
+    assert_eq!(items, (0..50).collect::<Vec<u32>>());
+}
+
+#[test]
+fn test_size_records() {
+    assert_eq!(Size::Records(12).records(1000), 12);
+    assert_eq!(Size::Scale(10).records(1000), 10000);
+}
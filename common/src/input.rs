@@ -0,0 +1,191 @@
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// File name each puzzle crate keeps its input in.
+pub const DEFAULT_FILE_NAME: &str = "input";
+
+/// Argument value used to request the input is read from stdin.
+pub const STDIN_ARG: &str = "-";
+
+#[derive(Debug, Clone, PartialEq)]
+/// Puzzle input, normalised so that every puzzle sees the same shape of text regardless of where
+/// it came from. Windows line endings are converted and any trailing newlines are dropped.
+pub struct Input {
+    raw: String,
+}
+
+impl Input {
+    /// Wrap an input already held in memory, such as an example embedded in a test.
+    pub fn new(raw: &str) -> Input {
+        Input {
+            raw: normalise(raw),
+        }
+    }
+
+    /// Read the input from the file at the provided path.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Input, InputError> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|err| InputError::new(Source::Path(path), err))?;
+        Input::read(file, Source::Path(path))
+    }
+
+    /// Read the input from stdin until it is closed.
+    pub fn from_stdin() -> Result<Input, InputError> {
+        let stdin = io::stdin();
+        let handle = stdin.lock();
+        Input::read(handle, Source::Stdin)
+    }
+
+    /// Read the input from wherever the first command line argument points. A `-` reads from
+    /// stdin and no argument falls back to the `input` file in the current directory.
+    pub fn from_args() -> Result<Input, InputError> {
+        match env::args().nth(1) {
+            Some(ref arg) if arg == STDIN_ARG => Input::from_stdin(),
+            Some(arg) => Input::from_path(arg),
+            None => Input::from_path(DEFAULT_FILE_NAME),
+        }
+    }
+
+    fn read<R: Read>(mut reader: R, source: Source) -> Result<Input, InputError> {
+        let mut contents = String::new();
+        reader
+            .read_to_string(&mut contents)
+            .map_err(|err| InputError::new(source, err))?;
+
+        Ok(Input::new(&contents))
+    }
+
+    /// The whole input as a single string.
+    pub fn as_str(&self) -> &str {
+        self.raw.as_str()
+    }
+
+    /// Each line of the input.
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.raw.lines()
+    }
+
+    /// Group the lines of the input into records separated by one or more blank lines.
+    ///
+    /// The input looks like: -
+    ///
+    /// ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+    /// byr:1937 iyr:2017 cid:147 hgt:183cm
+    ///
+    /// iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+    /// hcl:#cfa07d byr:1929
+    pub fn records(&self) -> Vec<Vec<&str>> {
+        let mut records: Vec<Vec<&str>> = vec![];
+        let mut record: Vec<&str> = vec![];
+
+        for line in self.raw.lines() {
+            if line.trim().is_empty() {
+                if !record.is_empty() {
+                    records.push(record);
+                    record = vec![];
+                }
+                continue;
+            }
+            record.push(line);
+        }
+        if !record.is_empty() {
+            records.push(record);
+        }
+
+        records
+    }
+
+    /// The input as rows of characters, one row per line.
+    pub fn grid(&self) -> Vec<Vec<char>> {
+        self.raw.lines().map(|line| line.chars().collect()).collect()
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// Convert Windows line endings and drop any trailing newlines.
+fn normalise(raw: &str) -> String {
+    raw.replace("\r\n", "\n").trim_end_matches('\n').to_string()
+}
+
+#[test]
+fn test_normalise_trailing_newlines() {
+    assert_eq!(Input::new("abc\n\n").as_str(), "abc");
+    assert_eq!(Input::new("abc").as_str(), "abc");
+}
+
+#[test]
+fn test_normalise_crlf() {
+    let input = Input::new("1, 2\r\n3, 4\r\n");
+    assert_eq!(input.as_str(), "1, 2\n3, 4");
+    assert_eq!(input.lines().collect::<Vec<&str>>(), vec!["1, 2", "3, 4"]);
+}
+
+#[test]
+fn test_records() {
+    let input = Input::new("a:1 b:2\nc:3\n\n\nd:4\n\ne:5 f:6\n");
+    assert_eq!(
+        input.records(),
+        vec![vec!["a:1 b:2", "c:3"], vec!["d:4"], vec!["e:5 f:6"]]
+    );
+}
+
+#[test]
+fn test_grid() {
+    let input = Input::new("..#\n#..\n");
+    assert_eq!(input.grid(), vec![vec!['.', '.', '#'], vec!['#', '.', '.']]);
+}
+
+#[test]
+fn test_from_path_missing_file() {
+    let err = Input::from_path("does_not_exist").unwrap_err();
+    assert_eq!(err.path, Some(PathBuf::from("does_not_exist")));
+    assert_eq!(err.error.kind(), io::ErrorKind::NotFound);
+}
+
+/// Where an input was being read from.
+enum Source<'a> {
+    Path(&'a Path),
+    Stdin,
+}
+
+#[derive(Debug)]
+/// Failure to read the puzzle input. The path is recorded when the input was read from a file.
+pub struct InputError {
+    pub path: Option<PathBuf>,
+    pub error: io::Error,
+}
+
+impl InputError {
+    fn new(source: Source, error: io::Error) -> InputError {
+        let path = match source {
+            Source::Path(path) => Some(path.to_path_buf()),
+            Source::Stdin => None,
+        };
+        InputError { path, error }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "couldn't read {}: {}", path.display(), self.error),
+            None => write!(f, "couldn't read stdin: {}", self.error),
+        }
+    }
+}
+
+impl error::Error for InputError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
@@ -0,0 +1,9 @@
+//! Pieces shared by every puzzle crate in the workspace.
+
+pub mod answers;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod generate;
+pub mod input;
+pub mod parse;
+pub mod solution;
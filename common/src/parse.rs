@@ -0,0 +1,87 @@
+use std::error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Part of the input a puzzle couldn't make sense of. The line is counted from 1 so it matches what
+/// an editor shows, it is 0 when the problem isn't with any one line.
+pub struct ParseError {
+    pub line: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    /// Record why the text couldn't be parsed. The line it was found on is filled in with
+    /// `on_line`, `parse_lines` takes care of this for anything parsed a line at a time.
+    pub fn new<R: Into<String>>(text: &str, reason: R) -> ParseError {
+        ParseError {
+            line: 0,
+            text: text.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Note the line the problem was found on.
+    pub fn on_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line > 0 {
+            write!(f, "line {}: ", self.line)?;
+        }
+        write!(f, "{}", self.reason)?;
+        if !self.text.is_empty() {
+            write!(f, " in {:?}", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Parse each line of the input in turn, stopping at the first that can't be parsed and noting
+/// which line it was.
+pub fn parse_lines<'a, T, F>(input: &'a str, mut parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&'a str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|err| err.on_line(index + 1)))
+        .collect()
+}
+
+#[test]
+fn test_parse_lines() {
+    let parsed = parse_lines("1\n2\n3", |line| {
+        line.parse::<i32>()
+            .map_err(|err| ParseError::new(line, err.to_string()))
+    });
+    assert_eq!(parsed, Ok(vec![1, 2, 3]));
+}
+
+#[test]
+fn test_parse_lines_reports_the_line() {
+    let err = parse_lines("1\n2\nthree", |line| {
+        line.parse::<i32>()
+            .map_err(|err| ParseError::new(line, err.to_string()))
+    })
+    .unwrap_err();
+    assert_eq!(err.line, 3);
+    assert_eq!(err.text, "three");
+    assert_eq!(
+        err.to_string(),
+        "line 3: invalid digit found in string in \"three\""
+    );
+}
+
+#[test]
+fn test_display_without_line() {
+    let err = ParseError::new("", "no journal entries found");
+    assert_eq!(err.to_string(), "no journal entries found");
+}
@@ -0,0 +1,225 @@
+use crate::input::Input;
+use crate::parse::ParseError;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The answer to one part of a puzzle. Most puzzles want a number but some spell out a word or a
+/// sequence of steps.
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    /// The part hasn't been solved yet.
+    Unsolved,
+    /// The part couldn't be answered for the reason given, such as a calculation overflowing.
+    Failed(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "not solved yet"),
+            Answer::Failed(reason) => write!(f, "failed, {}", reason),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Answer {
+        Answer::Number(n)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Answer {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Answer {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Answer {
+        Answer::Number(n as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// A day's puzzle. The input is parsed once into whatever shape suits the puzzle and both parts
+/// are answered from that. Parsing is the only step allowed to fail, anything malformed in the
+/// input should be caught there.
+pub trait Solution {
+    /// The input once parsed. This may borrow from the input it was parsed from.
+    type Parsed<'a>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>, ParseError>;
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Answer;
+
+    fn part2(_parsed: &Self::Parsed<'_>) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The answers to the requested parts of a puzzle and how long it took to find them.
+pub struct Solved {
+    pub answers: Vec<(u8, Answer)>,
+    pub timings: Timings,
+}
+
+/// Parse the input then answer each of the requested parts, in the order requested. Each stage
+/// is timed as it goes.
+pub fn solve<S: Solution>(input: &Input, parts: &[u8]) -> Result<Solved, ParseError> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let mut solved = Solved {
+        answers: vec![],
+        timings: Timings {
+            parse: start.elapsed(),
+            parts: vec![],
+        },
+    };
+
+    for part in parts {
+        let start = Instant::now();
+        let answer = match part {
+            1 => S::part1(&parsed),
+            _ => S::part2(&parsed),
+        };
+        solved.timings.parts.push((*part, start.elapsed()));
+        solved.answers.push((*part, answer));
+    }
+    Ok(solved)
+}
+
+/// Print the answer to both parts of the puzzle, this is all most of the day binaries do.
+pub fn print_answers<S: Solution>(input: &Input) -> Result<(), ParseError> {
+    for (part, answer) in solve::<S>(input, &[1, 2])?.answers {
+        println!("part {}: {}", part, answer);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+/// How long each stage of a puzzle took: parsing the input then each of the requested parts.
+pub struct Timings {
+    pub parse: Duration,
+    pub parts: Vec<(u8, Duration)>,
+}
+
+impl Timings {
+    /// Time taken for the part, if it was run.
+    pub fn part(&self, part: u8) -> Option<Duration> {
+        self.parts.iter().find(|(p, _)| *p == part).map(|(_, d)| *d)
+    }
+
+    /// Time taken for every stage together.
+    pub fn total(&self) -> Duration {
+        self.parse + self.parts.iter().map(|(_, d)| *d).sum::<Duration>()
+    }
+}
+
+/// Time parsing the input and each requested part, repeating every stage the requested number of
+/// times and keeping the fastest so that a single noisy run doesn't skew the result.
+pub fn time<S: Solution>(input: &Input, parts: &[u8], runs: u32) -> Result<Timings, ParseError> {
+    let runs = runs.max(1);
+    let parsed = S::parse(input)?;
+    let mut timings = Timings {
+        parse: fastest(runs, || {
+            let _ = S::parse(input);
+        }),
+        parts: vec![],
+    };
+
+    for part in parts {
+        let taken = fastest(runs, || match part {
+            1 => {
+                S::part1(&parsed);
+            }
+            _ => {
+                S::part2(&parsed);
+            }
+        });
+        timings.parts.push((*part, taken));
+    }
+    Ok(timings)
+}
+
+fn fastest<F: FnMut()>(runs: u32, mut stage: F) -> Duration {
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            stage();
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_answer_display() {
+    assert_eq!(Answer::from(9406609920_i64).to_string(), "9406609920");
+    assert_eq!(Answer::from("CABDFE").to_string(), "CABDFE");
+    assert_eq!(Answer::Unsolved.to_string(), "not solved yet");
+    assert_eq!(
+        Answer::Failed("overflowed".to_string()).to_string(),
+        "failed, overflowed"
+    );
+}
+
+#[cfg(test)]
+/// Counts the lines of the input for part 1 and leaves part 2 unsolved.
+struct LineCount;
+
+#[cfg(test)]
+impl Solution for LineCount {
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn parse(input: &Input) -> Result<Vec<&str>, ParseError> {
+        Ok(input.lines().collect())
+    }
+
+    fn part1(parsed: &Vec<&str>) -> Answer {
+        parsed.len().into()
+    }
+}
+
+#[test]
+fn test_solve_requested_parts() {
+    let input = Input::new("a\nb\nc\n");
+    let solved = solve::<LineCount>(&input, &[2, 1]).unwrap();
+    assert_eq!(
+        solved.answers,
+        vec![(2, Answer::Unsolved), (1, Answer::Number(3))]
+    );
+    assert_eq!(solved.timings.parts.len(), 2);
+    assert!(solved.timings.part(1).is_some());
+}
+
+#[test]
+fn test_time_requested_parts() {
+    let input = Input::new("a\nb\nc\n");
+    let timings = time::<LineCount>(&input, &[1], 3).unwrap();
+    assert_eq!(timings.parts.len(), 1);
+    assert!(timings.part(1).is_some());
+    assert_eq!(timings.part(2), None);
+    assert!(timings.total() >= timings.parse);
+}